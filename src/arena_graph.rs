@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::marker::PhantomData;
 use std::ops::Index;
 
@@ -189,6 +190,100 @@ impl<'a, T, A: ArenaBehavior> ArenaGraph<'a, T, A> {
                 .collect()
             )
     }
+
+    /// Perform lexicographically smallest topological sort on the graph
+    ///
+    /// If the graph contains no cycles, finds the topological ordering of this
+    /// graph that always emits the vertex with the smallest id first and
+    /// returns it as `Some(sorted)`.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// The difference between this function and
+    /// `IndexGraph::toposort_lexical()` is that this function returns
+    /// `id-arena` ids instead of indices.
+    ///
+    /// For examples, see `IndexGraph::toposort_lexical()`
+    pub fn toposort_lexical(self) -> Option<Vec<A::Id>> {
+        let arena_id = self.arena_id;
+
+        self.graph.toposort_lexical()
+            .map(|sorted| sorted.into_iter()
+                .map(|idx| A::new_id(arena_id, idx))
+                .collect()
+            )
+    }
+
+    /// Perform topological sort on the graph, choosing ready vertices with a
+    /// comparator
+    ///
+    /// If the graph contains no cycles, finds the topological ordering of this
+    /// graph that always emits the smallest ready vertex according to
+    /// `compare` first and returns it as `Some(sorted)`.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// The difference between this function and `IndexGraph::toposort_by()` is
+    /// that this function passes and returns `id-arena` ids instead of indices.
+    ///
+    /// For examples, see `IndexGraph::toposort_by()`
+    pub fn toposort_by<F>(self, mut compare: F) -> Option<Vec<A::Id>>
+        where F: FnMut(&A::Id, &A::Id) -> Ordering
+    {
+        let arena_id = self.arena_id;
+
+        self.graph.toposort_by(|&a, &b| compare(&A::new_id(arena_id, a), &A::new_id(arena_id, b)))
+            .map(|sorted| sorted.into_iter()
+                .map(|idx| A::new_id(arena_id, idx))
+                .collect()
+            )
+    }
+
+    /// Perform topological sort on the graph, choosing ready vertices with a
+    /// key extraction function
+    ///
+    /// If the graph contains no cycles, finds the topological ordering of this
+    /// graph that always emits the ready vertex with the smallest key first and
+    /// returns it as `Some(sorted)`.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// The difference between this function and `IndexGraph::toposort_by_key()`
+    /// is that this function passes and returns `id-arena` ids instead of
+    /// indices.
+    ///
+    /// For examples, see `IndexGraph::toposort_by_key()`
+    pub fn toposort_by_key<K, F>(self, mut f: F) -> Option<Vec<A::Id>>
+        where F: FnMut(&A::Id) -> K, K: Ord
+    {
+        self.toposort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Perform topological sort on the graph, grouping vertices into levels
+    ///
+    /// If the graph contains no cycles, finds the topological ordering of this
+    /// graph grouped into levels of vertices that only depend on vertices in
+    /// earlier levels and returns it as `Some(levels)`.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// The difference between this function and `IndexGraph::toposort_levels()`
+    /// is that this function returns `id-arena` ids instead of indices.
+    ///
+    /// For examples, see `IndexGraph::toposort_levels()`
+    pub fn toposort_levels(self) -> Option<Vec<Vec<A::Id>>> {
+        let arena_id = self.arena_id;
+
+        self.graph.toposort_levels()
+            .map(|levels| levels.into_iter()
+                .map(|level| level.into_iter()
+                    .map(|idx| A::new_id(arena_id, idx))
+                    .collect()
+                )
+                .collect()
+            )
+    }
+
     /// Find strongly connected components
     ///
     /// Finds the strongly connected components of this graph using Kosaraju's
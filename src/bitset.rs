@@ -0,0 +1,42 @@
+/// A fixed-size set of indices stored as a bit vector
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    /// Create a new empty set that can hold indices up to `len`
+    pub(crate) fn new(len: usize) -> Self {
+        // usize::div_ceil is not available on older compilers
+        #[allow(clippy::manual_div_ceil)]
+        let words = (len + 63) / 64;
+
+        BitSet { words: vec![0; words] }
+    }
+
+    /// Add an index to the set
+    pub(crate) fn insert(&mut self, idx: usize) {
+        self.words[idx / 64] |= 1 << (idx % 64);
+    }
+
+    /// Returns `true` if the set contains the index
+    pub(crate) fn contains(&self, idx: usize) -> bool {
+        self.words[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// Add every index of another set of the same size to this set
+    pub(crate) fn union_with(&mut self, other: &BitSet) {
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word |= other_word;
+        }
+    }
+
+    /// Returns an iterator over the indices in the set, in increasing order
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(word_idx, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| word_idx * 64 + bit)
+        })
+    }
+}
@@ -0,0 +1,115 @@
+use std::error::Error;
+use std::fmt;
+
+use super::IndexGraph;
+
+/// An error returned by `IndexGraph::check_order()`
+///
+/// Lists every reason why the given ordering is not a valid topological
+/// ordering of the graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderError {
+    /// Edges `(from, to)` where `to` does not appear after `from`
+    pub violations: Vec<(usize, usize)>,
+    /// Vertices of the graph that do not appear in the ordering
+    pub missing: Vec<usize>,
+    /// Vertices that appear more than once in the ordering
+    pub duplicates: Vec<usize>,
+    /// Indices in the ordering that are not vertices of the graph
+    pub out_of_range: Vec<usize>,
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = [
+            (self.violations.len(), "violated edges"),
+            (self.missing.len(), "missing vertices"),
+            (self.duplicates.len(), "duplicate vertices"),
+            (self.out_of_range.len(), "vertices out of range"),
+        ];
+
+        write!(f, "invalid topological ordering")?;
+
+        let mut separator = ": ";
+        for &(count, what) in counts.iter().filter(|&&(count, _)| count != 0) {
+            write!(f, "{}{} {}", separator, count, what)?;
+            separator = ", ";
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for OrderError {}
+
+impl IndexGraph {
+    /// Check whether an ordering is a valid topological ordering of the graph
+    ///
+    /// Returns `Ok(())` if `order` contains every vertex of the graph exactly
+    /// once and every edge goes from a vertex to one that appears later in
+    /// `order`.
+    ///
+    /// Otherwise, returns an `OrderError` listing every violated edge, every
+    /// missing or duplicate vertex and every index that is out of range. For
+    /// duplicate vertices, only their first occurrence is used to check edges,
+    /// and edges from or to missing vertices are not reported as violated.
+    ///
+    /// This method runs in `O(V + E)` time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// assert_eq!(g.check_order(&[0, 3, 1, 2]), Ok(()));
+    ///
+    /// let err = g.check_order(&[1, 0, 2, 2, 5]).unwrap_err();
+    /// assert_eq!(err.violations, vec![(0, 1)]);
+    /// assert_eq!(err.missing, vec![3]);
+    /// assert_eq!(err.duplicates, vec![2]);
+    /// assert_eq!(err.out_of_range, vec![5]);
+    /// ```
+    pub fn check_order(&self, order: &[usize]) -> Result<(), OrderError> {
+        let len = self.vertices.len();
+        let mut error = OrderError::default();
+
+        // find position of every vertex in the ordering
+        let mut position = vec![usize::MAX; len];
+        for (pos, &idx) in order.iter().enumerate() {
+            if idx >= len {
+                error.out_of_range.push(idx);
+            } else if position[idx] != usize::MAX {
+                error.duplicates.push(idx);
+            } else {
+                position[idx] = pos;
+            }
+        }
+
+        // check every edge of every vertex in the ordering
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            if position[idx] == usize::MAX {
+                error.missing.push(idx);
+                continue
+            }
+
+            for &next_idx in &vertex.out_edges {
+                if position[next_idx] != usize::MAX && position[next_idx] <= position[idx] {
+                    error.violations.push((idx, next_idx));
+                }
+            }
+        }
+
+        if error == OrderError::default() {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::ops::Range;
+
+use super::IndexGraph;
+use super::SccPartition;
+
+/// The condensation of an `IndexGraph`
+///
+/// Every strongly connected component of the original graph is collapsed into
+/// a single vertex of the condensed graph, which is acyclic by construction.
+/// Components are numbered in topological order, so vertex `c` of the
+/// condensed graph corresponds to component `c` of the underlying
+/// `SccPartition`.
+///
+/// See `IndexGraph::condensation()` for usage examples
+#[derive(Debug, Clone)]
+pub struct Condensation {
+    graph: IndexGraph,
+    partition: SccPartition,
+}
+
+impl Condensation {
+    /// Returns a reference to the condensed graph
+    pub fn graph(&self) -> &IndexGraph {
+        &self.graph
+    }
+
+    /// Returns the condensed graph
+    pub fn into_graph(self) -> IndexGraph {
+        self.graph
+    }
+
+    /// Returns a reference to the partition the condensed graph was built from
+    pub fn partition(&self) -> &SccPartition {
+        &self.partition
+    }
+
+    /// Returns the partition the condensed graph was built from
+    pub fn into_partition(self) -> SccPartition {
+        self.partition
+    }
+
+    /// Returns the number of components, which is the number of vertices in
+    /// the condensed graph
+    pub fn len(&self) -> usize {
+        self.partition.len()
+    }
+
+    /// Returns `true` if the condensed graph has no vertices
+    pub fn is_empty(&self) -> bool {
+        self.partition.is_empty()
+    }
+
+    /// Returns the id of the component the given vertex of the original graph
+    /// was collapsed into
+    pub fn component_of(&self, vertex: usize) -> usize {
+        self.partition.component_of(vertex)
+    }
+
+    /// Returns the vertices of the original graph that were collapsed into the
+    /// component with the given id
+    pub fn component(&self, id: usize) -> &[usize] {
+        self.partition.component(id)
+    }
+
+    /// Returns a topological ordering of the components
+    ///
+    /// Since components are numbered in topological order, this is always the
+    /// range of all component ids.
+    pub fn toposort(&self) -> Range<usize> {
+        0..self.partition.len()
+    }
+}
+
+impl IndexGraph {
+    /// Collapse every strongly connected component into a single vertex
+    ///
+    /// Finds the strongly connected components of this graph using
+    /// `.scc_partition()` and builds a new graph with one vertex per
+    /// component. For every edge between vertices in two different components,
+    /// the condensed graph contains a single edge between those components.
+    /// Edges inside a component are dropped, so the condensed graph is always
+    /// acyclic.
+    ///
+    /// # Example
+    ///
+    /// This example creates a graph of modules in which modules 1 and 2 depend
+    /// on each other, and collapses them into a single unit.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2],
+    ///     vec![1, 3],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let condensation = g.condensation();
+    ///
+    /// assert_eq!(condensation.len(), 3);
+    /// assert_eq!(condensation.component(1), &[1, 2]);
+    /// assert_eq!(condensation.component_of(2), 1);
+    /// assert_eq!(condensation.toposort().collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(condensation.into_graph().toposort(), Some(vec![0, 1, 2]));
+    /// ```
+    pub fn condensation(&self) -> Condensation {
+        let partition = self.scc_partition();
+        let mut graph = IndexGraph::with_vertices(partition.len());
+
+        // remember the last component an edge was added from, so that
+        // duplicate edges between components are only added once
+        let mut last_from = vec![usize::MAX; partition.len()];
+
+        for (id, component) in partition.components().iter().enumerate() {
+            for &idx in component {
+                for &next_idx in &self.vertices[idx].out_edges {
+                    let next_id = partition.component_of(next_idx);
+
+                    if next_id != id && last_from[next_id] != id {
+                        last_from[next_id] = id;
+                        graph.add_edge(id, next_id);
+                    }
+                }
+            }
+        }
+
+        Condensation { graph, partition }
+    }
+}
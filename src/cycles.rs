@@ -0,0 +1,473 @@
+use std::collections::VecDeque as Queue;
+use std::iter::FusedIterator;
+
+use super::IndexGraph;
+use super::SccPartition;
+
+impl IndexGraph {
+    /// Internal method that finds a shortest cycle through a vertex
+    ///
+    /// Performs a breadth-first search from `start`, restricted to the
+    /// strongly connected component of `start`, and returns the first cycle
+    /// back to `start` as a path `[start, ..., start]`.
+    ///
+    /// `parent` is used as scratch space and must contain `usize::MAX` for
+    /// every vertex. It is restored to that state before returning.
+    fn cycle_through_internal(&self, start: usize, partition: &SccPartition, parent: &mut [usize]) -> Option<Vec<usize>> {
+        let id = partition.component_of(start);
+        let mut queue = Queue::new();
+        let mut visited = vec![start];
+        let mut cycle = None;
+
+        parent[start] = start;
+        queue.push_back(start);
+
+        'search: while let Some(idx) = queue.pop_front() {
+            for &next_idx in &self.vertices[idx].out_edges {
+                if next_idx == start {
+                    // walk back along the search tree to reconstruct the path
+                    let mut path = vec![start];
+                    let mut cur_idx = idx;
+                    while cur_idx != start {
+                        path.push(cur_idx);
+                        cur_idx = parent[cur_idx];
+                    }
+                    path.push(start);
+                    path.reverse();
+
+                    cycle = Some(path);
+                    break 'search
+                }
+
+                if partition.component_of(next_idx) == id && parent[next_idx] == usize::MAX {
+                    parent[next_idx] = idx;
+                    visited.push(next_idx);
+                    queue.push_back(next_idx);
+                }
+            }
+        }
+
+        for idx in visited {
+            parent[idx] = usize::MAX;
+        }
+
+        cycle
+    }
+
+    /// Find a cycle in the graph
+    ///
+    /// If the graph contains cycles, returns one of them as the path of
+    /// vertices along its edges as `Some(cycle)`. The first and last element
+    /// of the path are the same vertex. The cycle is the shortest cycle
+    /// through the first vertex of the first strongly connected component
+    /// that contains a cycle.
+    ///
+    /// If the graph contains no cycles, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// This example creates an `IndexGraph` of the example graph from the
+    /// Wikipedia page for
+    /// [Topological sorting](https://en.wikipedia.org/wiki/Topological_sorting)
+    /// with an additional edge that creates a cycle, and finds that cycle.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![3],
+    ///     vec![3, 4],
+    ///     vec![4, 7],
+    ///     vec![5, 6, 7],
+    ///     vec![6],
+    ///     vec![],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let mut g2 = g.clone();
+    /// g2.add_edge(6, 2); // cycle [2, 4, 6]
+    ///
+    /// assert_eq!(g.find_cycle(), None);
+    /// assert_eq!(g2.find_cycle(), Some(vec![6, 2, 4, 6]));
+    /// ```
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        let partition = self.scc_partition();
+        let id = (0..partition.len()).find(|&id| partition.is_cyclic(id))?;
+        let mut parent = vec![usize::MAX; self.vertices.len()];
+
+        self.cycle_through_internal(partition.component(id)[0], &partition, &mut parent)
+    }
+
+    /// Find a shortest cycle through a vertex
+    ///
+    /// If the given vertex is part of a cycle, returns a shortest cycle
+    /// through it as the path of vertices along its edges as `Some(cycle)`.
+    /// The path starts and ends with the given vertex. The cycle is found by
+    /// breadth-first search restricted to the strongly connected component of
+    /// the vertex.
+    ///
+    /// If the vertex is not part of any cycle, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2, 3],
+    ///     vec![3],
+    ///     vec![0, 4],
+    ///     vec![]
+    /// ]);
+    ///
+    /// assert_eq!(g.shortest_cycle_through(2), Some(vec![2, 3, 0, 1, 2]));
+    /// assert_eq!(g.shortest_cycle_through(0), Some(vec![0, 1, 3, 0]));
+    /// assert_eq!(g.shortest_cycle_through(4), None);
+    /// ```
+    pub fn shortest_cycle_through(&self, vertex: usize) -> Option<Vec<usize>> {
+        let partition = self.scc_partition();
+        let mut parent = vec![usize::MAX; self.vertices.len()];
+
+        self.cycle_through_internal(vertex, &partition, &mut parent)
+    }
+
+    /// Find a shortest cycle in every strongly connected component
+    ///
+    /// For every strongly connected component that contains a cycle, returns
+    /// a shortest cycle in it as the path of vertices along its edges. The
+    /// first and last element of every path are the same vertex. Cycles are
+    /// returned in topological order of their components.
+    ///
+    /// A breadth-first search is performed from every vertex of every
+    /// component, so this method runs in `O(V * E)` time in the worst case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2, 3],
+    ///     vec![3],
+    ///     vec![0, 4],
+    ///     vec![5],
+    ///     vec![4, 5]
+    /// ]);
+    ///
+    /// assert_eq!(g.shortest_cycles(), vec![vec![0, 1, 3, 0], vec![5, 5]]);
+    /// ```
+    pub fn shortest_cycles(&self) -> Vec<Vec<usize>> {
+        let partition = self.scc_partition();
+        let mut parent = vec![usize::MAX; self.vertices.len()];
+        let mut cycles = Vec::new();
+
+        for id in (0..partition.len()).filter(|&id| partition.is_cyclic(id)) {
+            let mut shortest: Option<Vec<usize>> = None;
+
+            for &idx in partition.component(id) {
+                let cycle = self.cycle_through_internal(idx, &partition, &mut parent);
+
+                if let Some(cycle) = cycle {
+                    if cycle.len() < shortest.as_ref().map_or(usize::MAX, Vec::len) {
+                        shortest = Some(cycle);
+                    }
+                }
+
+                // a self-loop is the shortest possible cycle
+                if shortest.as_ref().map_or(0, Vec::len) == 2 {
+                    break
+                }
+            }
+
+            cycles.extend(shortest);
+        }
+
+        cycles
+    }
+}
+
+/// A strongly connected subgraph of an `IndexGraph` searched for cycles
+#[derive(Debug, Clone)]
+struct Subgraph {
+    /// Original index of every vertex in the subgraph
+    vertices: Vec<usize>,
+    graph: IndexGraph,
+}
+
+/// The state of a search for all cycles through the first vertex of a
+/// subgraph
+#[derive(Debug, Clone)]
+struct CircuitSearch {
+    sub: Subgraph,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    path: Vec<usize>,
+    dfs_stack: Vec<(usize, usize, bool)>,
+}
+
+impl CircuitSearch {
+    fn new(sub: Subgraph) -> Self {
+        let len = sub.vertices.len();
+        let mut blocked = vec![false; len];
+        blocked[0] = true;
+
+        CircuitSearch {
+            sub,
+            blocked,
+            blocked_by: vec![Vec::new(); len],
+            path: vec![0],
+            dfs_stack: vec![(0, 0, false)],
+        }
+    }
+
+    /// Unblock a vertex and every vertex that is blocked because of it
+    fn unblock(&mut self, idx: usize) {
+        let mut stack = vec![idx];
+
+        while let Some(idx) = stack.pop() {
+            if self.blocked[idx] {
+                self.blocked[idx] = false;
+                stack.append(&mut self.blocked_by[idx]);
+            }
+        }
+    }
+
+    /// Continue the search until the next cycle is found
+    ///
+    /// Returns `None` once all cycles through the first vertex were found.
+    fn next_cycle(&mut self, max_length: usize) -> Option<Vec<usize>> {
+        while let Some(&mut (idx, ref mut edge_idx, ref mut found)) = self.dfs_stack.last_mut() {
+            let out_edges = &self.sub.graph.vertices[idx].out_edges;
+
+            if *edge_idx < out_edges.len() {
+                let next_idx = out_edges[*edge_idx];
+                *edge_idx += 1;
+
+                if next_idx == 0 {
+                    *found = true;
+
+                    let mut cycle: Vec<_> = self.path.iter()
+                        .map(|&idx| self.sub.vertices[idx])
+                        .collect();
+                    cycle.push(self.sub.vertices[0]);
+
+                    return Some(cycle)
+                } else if !self.blocked[next_idx] {
+                    if self.path.len() < max_length {
+                        self.blocked[next_idx] = true;
+                        self.path.push(next_idx);
+                        self.dfs_stack.push((next_idx, 0, false));
+                    } else {
+                        // cycles through this edge may exist, but are too
+                        // long. treat as found so that vertices are unblocked
+                        *found = true;
+                    }
+                }
+            } else {
+                let found = *found;
+                self.dfs_stack.pop();
+                self.path.pop();
+
+                if found {
+                    self.unblock(idx);
+                } else {
+                    for &next_idx in &self.sub.graph.vertices[idx].out_edges {
+                        if !self.blocked_by[next_idx].contains(&idx) {
+                            self.blocked_by[next_idx].push(idx);
+                        }
+                    }
+                }
+
+                if let Some((_, _, parent_found)) = self.dfs_stack.last_mut() {
+                    *parent_found |= found;
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// An iterator over all elementary cycles of an `IndexGraph`
+///
+/// Created by `IndexGraph::elementary_cycles()`. Cycles are found lazily using
+/// Johnson's algorithm.
+#[derive(Debug, Clone)]
+pub struct ElementaryCycles<'g> {
+    graph: &'g IndexGraph,
+    max_length: usize,
+    self_loop_idx: usize,
+    self_loop_edge_idx: usize,
+    worklist: Vec<Subgraph>,
+    search: Option<CircuitSearch>,
+}
+
+impl<'g> ElementaryCycles<'g> {
+    fn new(graph: &'g IndexGraph, max_length: usize) -> Self {
+        let partition = graph.scc_partition();
+        let mut local = vec![usize::MAX; graph.vertices.len()];
+
+        let worklist = partition.components().iter()
+            .rev()
+            .filter(|component| component.len() > 1)
+            .map(|component| Subgraph {
+                vertices: component.clone(),
+                graph: graph.induced_subgraph_internal(component, &mut local),
+            })
+            .collect();
+
+        ElementaryCycles {
+            graph,
+            max_length,
+            self_loop_idx: 0,
+            self_loop_edge_idx: 0,
+            worklist,
+            search: None,
+        }
+    }
+}
+
+impl Iterator for ElementaryCycles<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        // self-loops are not part of the subgraphs and are reported first,
+        // once for every self-loop edge
+        while self.max_length >= 1 && self.self_loop_idx < self.graph.vertices.len() {
+            let idx = self.self_loop_idx;
+            let out_edges = &self.graph.vertices[idx].out_edges;
+
+            if self.self_loop_edge_idx >= out_edges.len() {
+                self.self_loop_idx += 1;
+                self.self_loop_edge_idx = 0;
+                continue
+            }
+
+            let next_idx = out_edges[self.self_loop_edge_idx];
+            self.self_loop_edge_idx += 1;
+
+            if next_idx == idx {
+                return Some(vec![idx, idx])
+            }
+        }
+
+        loop {
+            let search = match &mut self.search {
+                Some(search) => search,
+                None => {
+                    let sub = self.worklist.pop()?;
+                    self.search.get_or_insert(CircuitSearch::new(sub))
+                }
+            };
+
+            if let Some(cycle) = search.next_cycle(self.max_length) {
+                return Some(cycle)
+            }
+
+            // all cycles through the first vertex were found, remove it and
+            // search the strongly connected components of the remaining
+            // subgraph
+            let sub = self.search.take()?.sub;
+            let len = sub.vertices.len();
+            let rest_vertices: Vec<_> = (1..len).collect();
+            let mut local = vec![usize::MAX; len];
+            let rest = sub.graph.induced_subgraph_internal(&rest_vertices, &mut local);
+
+            for component in rest.scc_tarjan() {
+                if component.len() > 1 {
+                    let mut local = vec![usize::MAX; len - 1];
+
+                    self.worklist.push(Subgraph {
+                        vertices: component.iter().map(|&idx| sub.vertices[idx + 1]).collect(),
+                        graph: rest.induced_subgraph_internal(&component, &mut local),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl FusedIterator for ElementaryCycles<'_> {}
+
+impl IndexGraph {
+    /// Internal method that builds the subgraph induced by a list of vertices
+    ///
+    /// Vertex `i` of the subgraph corresponds to `vertices[i]`. Self-loops are
+    /// not copied into the subgraph.
+    ///
+    /// `local` is used as scratch space and must contain `usize::MAX` for
+    /// every vertex. It is restored to that state before returning.
+    fn induced_subgraph_internal(&self, vertices: &[usize], local: &mut [usize]) -> IndexGraph {
+        let mut sub = IndexGraph::with_vertices(vertices.len());
+
+        for (local_idx, &idx) in vertices.iter().enumerate() {
+            local[idx] = local_idx;
+        }
+
+        for (local_idx, &idx) in vertices.iter().enumerate() {
+            for &next_idx in &self.vertices[idx].out_edges {
+                if next_idx != idx && local[next_idx] != usize::MAX {
+                    sub.add_edge(local_idx, local[next_idx]);
+                }
+            }
+        }
+
+        for &idx in vertices {
+            local[idx] = usize::MAX;
+        }
+
+        sub
+    }
+
+    /// Returns an iterator over all elementary cycles of the graph
+    ///
+    /// Finds every elementary cycle of the graph, i.e. every cycle that does
+    /// not visit a vertex more than once, using Johnson's algorithm within
+    /// every strongly connected component. Cycles are returned as paths of
+    /// vertices along their edges, with the same first and last vertex.
+    ///
+    /// Cycles are found lazily, so the number of cycles can be limited with
+    /// `.take()`. If `max_length` is `Some(len)`, only cycles with at most
+    /// `len` edges are returned.
+    ///
+    /// Parallel edges result in the same cycle being returned more than once,
+    /// including parallel self-loops.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![0, 2],
+    ///     vec![0, 2],
+    ///     vec![]
+    /// ]);
+    ///
+    /// assert_eq!(g.elementary_cycles(None).collect::<Vec<_>>(), vec![
+    ///     vec![2, 2],
+    ///     vec![0, 1, 0],
+    ///     vec![0, 1, 2, 0]
+    /// ]);
+    /// assert_eq!(g.elementary_cycles(Some(2)).count(), 2);
+    ///
+    /// let g2 = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![0, 0, 1, 1],
+    ///     vec![0]
+    /// ]);
+    ///
+    /// assert_eq!(g2.elementary_cycles(None).collect::<Vec<_>>(), vec![
+    ///     vec![0, 0],
+    ///     vec![0, 0],
+    ///     vec![0, 1, 0],
+    ///     vec![0, 1, 0]
+    /// ]);
+    /// ```
+    pub fn elementary_cycles(&self, max_length: Option<usize>) -> ElementaryCycles<'_> {
+        ElementaryCycles::new(self, max_length.unwrap_or(usize::MAX))
+    }
+}
@@ -0,0 +1,180 @@
+use super::IndexGraph;
+
+/// The kind of an edge in a depth-first search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// An edge to a vertex that was discovered through it
+    Tree,
+    /// An edge to an ancestor in the search tree, including self-loops
+    Back,
+    /// An edge to a descendant in the search tree that is not a tree edge
+    Forward,
+    /// Any other edge, to a vertex that was already finished and is not a
+    /// descendant
+    Cross,
+}
+
+/// The result of a depth-first search of an `IndexGraph`
+///
+/// Only vertices reachable from the roots of the search are visited.
+///
+/// See `IndexGraph::dfs()` for usage examples
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthFirstSearch {
+    preorder: Vec<usize>,
+    postorder: Vec<usize>,
+    preorder_number: Vec<usize>,
+    postorder_number: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    edges: Vec<(usize, usize, EdgeKind)>,
+}
+
+impl DepthFirstSearch {
+    /// Returns the visited vertices in the order they were discovered
+    pub fn preorder(&self) -> &[usize] {
+        &self.preorder
+    }
+
+    /// Returns the visited vertices in the order they were finished
+    pub fn postorder(&self) -> &[usize] {
+        &self.postorder
+    }
+
+    /// Returns the visited vertices in reverse post-order
+    ///
+    /// For acyclic graphs, this is a topological ordering of the visited
+    /// vertices.
+    pub fn reverse_postorder(&self) -> Vec<usize> {
+        self.postorder.iter().rev().copied().collect()
+    }
+
+    /// Returns `true` if the vertex was visited
+    pub fn is_visited(&self, vertex: usize) -> bool {
+        self.preorder_number[vertex] != usize::MAX
+    }
+
+    /// Returns the position of a vertex in pre-order, or `None` if it was not
+    /// visited
+    pub fn preorder_number(&self, vertex: usize) -> Option<usize> {
+        Some(self.preorder_number[vertex]).filter(|&number| number != usize::MAX)
+    }
+
+    /// Returns the position of a vertex in post-order, or `None` if it was not
+    /// visited
+    pub fn postorder_number(&self, vertex: usize) -> Option<usize> {
+        Some(self.postorder_number[vertex]).filter(|&number| number != usize::MAX)
+    }
+
+    /// Returns the position of a vertex in reverse post-order, or `None` if it
+    /// was not visited
+    pub fn reverse_postorder_number(&self, vertex: usize) -> Option<usize> {
+        self.postorder_number(vertex).map(|number| self.postorder.len() - 1 - number)
+    }
+
+    /// Returns the parent of a vertex in the search tree, or `None` for roots
+    /// and vertices that were not visited
+    pub fn parent(&self, vertex: usize) -> Option<usize> {
+        self.parent[vertex]
+    }
+
+    /// Returns every edge `(from, to, kind)` of the visited vertices, in the
+    /// order they were examined
+    pub fn edges(&self) -> &[(usize, usize, EdgeKind)] {
+        &self.edges
+    }
+}
+
+impl IndexGraph {
+    /// Perform a depth-first search from a list of roots
+    ///
+    /// Starts a depth-first search from every root that was not yet visited,
+    /// in the given order, following outgoing edges in the order they were
+    /// added. Records the pre-order and post-order of the visited vertices,
+    /// the search tree, and the kind of every examined edge.
+    ///
+    /// Reverse post-order, as used by many dataflow analyses, is available via
+    /// `.reverse_postorder()` on the result.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::{EdgeKind, IndexGraph};
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1, 3],
+    ///     vec![2],
+    ///     vec![0, 3],
+    ///     vec![],
+    ///     vec![3]
+    /// ]);
+    ///
+    /// let search = g.dfs(&[0, 4]);
+    ///
+    /// assert_eq!(search.preorder(), &[0, 1, 2, 3, 4]);
+    /// assert_eq!(search.postorder(), &[3, 2, 1, 0, 4]);
+    /// assert_eq!(search.reverse_postorder(), vec![4, 0, 1, 2, 3]);
+    /// assert_eq!(search.parent(3), Some(2));
+    /// assert_eq!(search.edges(), &[
+    ///     (0, 1, EdgeKind::Tree),
+    ///     (1, 2, EdgeKind::Tree),
+    ///     (2, 0, EdgeKind::Back),
+    ///     (2, 3, EdgeKind::Tree),
+    ///     (0, 3, EdgeKind::Forward),
+    ///     (4, 3, EdgeKind::Cross)
+    /// ]);
+    /// ```
+    pub fn dfs(&self, roots: &[usize]) -> DepthFirstSearch {
+        let len = self.vertices.len();
+
+        let mut search = DepthFirstSearch {
+            preorder: Vec::new(),
+            postorder: Vec::new(),
+            preorder_number: vec![usize::MAX; len],
+            postorder_number: vec![usize::MAX; len],
+            parent: vec![None; len],
+            edges: Vec::new(),
+        };
+
+        let mut dfs_stack = Vec::new();
+
+        for &root_idx in roots {
+            if search.is_visited(root_idx) {
+                continue
+            }
+
+            search.preorder_number[root_idx] = search.preorder.len();
+            search.preorder.push(root_idx);
+            dfs_stack.push((root_idx, 0));
+
+            while let Some((idx, edge_idx)) = dfs_stack.pop() {
+                if edge_idx < self.vertices[idx].out_edges.len() {
+                    dfs_stack.push((idx, edge_idx + 1));
+
+                    let next_idx = self.vertices[idx].out_edges[edge_idx];
+                    let kind = if !search.is_visited(next_idx) {
+                        search.preorder_number[next_idx] = search.preorder.len();
+                        search.preorder.push(next_idx);
+                        search.parent[next_idx] = Some(idx);
+                        dfs_stack.push((next_idx, 0));
+
+                        EdgeKind::Tree
+                    } else if search.postorder_number[next_idx] == usize::MAX {
+                        // discovered, but not finished, so still on the stack
+                        EdgeKind::Back
+                    } else if search.preorder_number[next_idx] > search.preorder_number[idx] {
+                        EdgeKind::Forward
+                    } else {
+                        EdgeKind::Cross
+                    };
+
+                    search.edges.push((idx, next_idx, kind));
+                } else {
+                    search.postorder_number[idx] = search.postorder.len();
+                    search.postorder.push(idx);
+                }
+            }
+        }
+
+        search
+    }
+}
@@ -0,0 +1,256 @@
+use super::IndexGraph;
+
+/// The dominator tree of an `IndexGraph` with an entry vertex
+///
+/// A vertex `a` dominates a vertex `b` if every path from the entry to `b`
+/// passes through `a`. Every vertex dominates itself. Vertices that are not
+/// reachable from the entry have no dominators.
+///
+/// See `IndexGraph::dominators()` for usage examples
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dominators {
+    entry: usize,
+    idom: Vec<Option<usize>>,
+    frontiers: Vec<Vec<usize>>,
+    preorder: Vec<usize>,
+    postorder: Vec<usize>,
+}
+
+impl Dominators {
+    /// Returns the entry vertex
+    pub fn entry(&self) -> usize {
+        self.entry
+    }
+
+    /// Returns `true` if the vertex is reachable from the entry
+    pub fn is_reachable(&self, vertex: usize) -> bool {
+        vertex == self.entry || self.idom[vertex].is_some()
+    }
+
+    /// Returns the immediate dominator of a vertex, which is its parent in the
+    /// dominator tree
+    ///
+    /// Returns `None` for the entry and for vertices that are not reachable
+    /// from the entry.
+    pub fn immediate_dominator(&self, vertex: usize) -> Option<usize> {
+        self.idom[vertex]
+    }
+
+    /// Returns the dominance frontier of a vertex, in increasing order
+    ///
+    /// The dominance frontier of `a` contains every vertex `b` that is not
+    /// strictly dominated by `a`, but has a predecessor that is dominated by
+    /// `a`.
+    pub fn dominance_frontier(&self, vertex: usize) -> &[usize] {
+        &self.frontiers[vertex]
+    }
+
+    /// Returns `true` if `a` dominates `b`
+    ///
+    /// This method runs in constant time.
+    pub fn dominates(&self, a: usize, b: usize) -> bool {
+        self.is_reachable(a) && self.is_reachable(b)
+            && self.preorder[a] <= self.preorder[b]
+            && self.postorder[b] <= self.postorder[a]
+    }
+
+    /// Returns `true` if `a` dominates `b` and `a` is not `b`
+    pub fn strictly_dominates(&self, a: usize, b: usize) -> bool {
+        a != b && self.dominates(a, b)
+    }
+}
+
+impl IndexGraph {
+    /// Internal method that returns the vertices reachable from a root in
+    /// depth-first post-order
+    fn dfs_postorder_internal(&self, root: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.vertices.len()];
+        let mut postorder = Vec::new();
+        let mut dfs_stack = vec![(root, 0)];
+        visited[root] = true;
+
+        while let Some((idx, edge_idx)) = dfs_stack.pop() {
+            if edge_idx < self.vertices[idx].out_edges.len() {
+                dfs_stack.push((idx, edge_idx + 1));
+
+                let next_idx = self.vertices[idx].out_edges[edge_idx];
+                if !visited[next_idx] {
+                    visited[next_idx] = true;
+                    dfs_stack.push((next_idx, 0));
+                }
+            } else {
+                postorder.push(idx);
+            }
+        }
+
+        postorder
+    }
+
+    /// Compute the dominator tree of the graph
+    ///
+    /// Finds the immediate dominator of every vertex reachable from `entry`
+    /// using the iterative algorithm by Cooper, Harvey and Kennedy, and the
+    /// dominance frontier of every vertex. The returned tree answers dominance
+    /// queries in constant time.
+    ///
+    /// Post-dominators can be computed by calling this method on a transposed
+    /// copy of the graph (see `.transpose()`) with the exit vertex as entry.
+    ///
+    /// # Example
+    ///
+    /// This example creates a control flow graph of a loop containing an
+    /// if-else statement.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],    // entry
+    ///     vec![2, 5], // loop header
+    ///     vec![3, 4], // if
+    ///     vec![6],    // then
+    ///     vec![6],    // else
+    ///     vec![],     // exit
+    ///     vec![1]     // end of loop body
+    /// ]);
+    ///
+    /// let dom = g.dominators(0);
+    ///
+    /// assert_eq!(dom.immediate_dominator(6), Some(2));
+    /// assert_eq!(dom.immediate_dominator(5), Some(1));
+    /// assert!(dom.dominates(1, 6));
+    /// assert!(!dom.dominates(3, 6));
+    /// assert_eq!(dom.dominance_frontier(3), &[6]);
+    /// assert_eq!(dom.dominance_frontier(6), &[1]);
+    ///
+    /// let mut t = g.clone();
+    /// t.transpose();
+    /// let post_dom = t.dominators(5);
+    ///
+    /// assert_eq!(post_dom.immediate_dominator(2), Some(6));
+    /// assert!(post_dom.dominates(1, 0));
+    /// ```
+    pub fn dominators(&self, entry: usize) -> Dominators {
+        let len = self.vertices.len();
+        let postorder = self.dfs_postorder_internal(entry);
+
+        let mut postorder_number = vec![usize::MAX; len];
+        for (number, &idx) in postorder.iter().enumerate() {
+            postorder_number[idx] = number;
+        }
+
+        // Cooper, Harvey and Kennedy's algorithm for dominators
+
+        // the entry is temporarily its own dominator
+        let mut idom = vec![None; len];
+        idom[entry] = Some(entry);
+
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while postorder_number[a] < postorder_number[b] {
+                    a = idom[a].expect("processed vertices have a dominator");
+                }
+                while postorder_number[b] < postorder_number[a] {
+                    b = idom[b].expect("processed vertices have a dominator");
+                }
+            }
+
+            a
+        };
+
+        // process vertices in reverse post-order until nothing changes
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for &idx in postorder.iter().rev().skip(1) {
+                let new_idom = self.vertices[idx].in_edges.iter()
+                    .copied()
+                    .filter(|&prev_idx| idom[prev_idx].is_some())
+                    .reduce(|a, b| intersect(&idom, a, b));
+
+                if new_idom.is_some() && idom[idx] != new_idom {
+                    idom[idx] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        // dominance frontiers, walking up from the predecessors of every join
+        // point to its immediate dominator
+        let mut frontiers = vec![Vec::new(); len];
+        for &idx in &postorder {
+            let preds: Vec<_> = self.vertices[idx].in_edges.iter()
+                .copied()
+                .filter(|&prev_idx| idom[prev_idx].is_some())
+                .collect();
+
+            // the entry is a join point if it has any predecessor, since it is
+            // also entered from outside of the graph
+            let is_join = preds.len() >= 2 || idx == entry && !preds.is_empty();
+            if !is_join {
+                continue
+            }
+
+            for prev_idx in preds {
+                let mut runner = prev_idx;
+                loop {
+                    if idx != entry && Some(runner) == idom[idx] {
+                        break
+                    }
+
+                    // every vertex is processed once, so duplicates are always
+                    // at the end of the list
+                    if frontiers[runner].last() != Some(&idx) {
+                        frontiers[runner].push(idx);
+                    }
+
+                    if runner == entry {
+                        break
+                    }
+                    runner = idom[runner].expect("reachable vertices have a dominator");
+                }
+            }
+        }
+
+        for frontier in &mut frontiers {
+            frontier.sort_unstable();
+        }
+
+        idom[entry] = None;
+
+        // number the dominator tree in pre-order and post-order, so that
+        // dominance can be checked by interval containment
+        let mut children = vec![Vec::new(); len];
+        for &idx in &postorder {
+            if let Some(parent_idx) = idom[idx] {
+                children[parent_idx].push(idx);
+            }
+        }
+
+        let mut preorder = vec![0; len];
+        let mut tree_postorder = vec![0; len];
+        let mut pre_count = 0;
+        let mut post_count = 0;
+
+        let mut dfs_stack = vec![(entry, 0)];
+        preorder[entry] = pre_count;
+        pre_count += 1;
+
+        while let Some((idx, child_idx)) = dfs_stack.pop() {
+            if child_idx < children[idx].len() {
+                dfs_stack.push((idx, child_idx + 1));
+
+                let next_idx = children[idx][child_idx];
+                preorder[next_idx] = pre_count;
+                pre_count += 1;
+                dfs_stack.push((next_idx, 0));
+            } else {
+                tree_postorder[idx] = post_count;
+                post_count += 1;
+            }
+        }
+
+        Dominators { entry, idom, frontiers, preorder, postorder: tree_postorder }
+    }
+}
@@ -0,0 +1,429 @@
+use std::collections::BinaryHeap;
+use std::cmp::Reverse;
+
+use super::IndexGraph;
+use super::SccPartition;
+
+/// A set of edges whose removal makes a graph acyclic
+///
+/// Returned by `IndexGraph::feedback_arc_set()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackArcSet {
+    /// Edges `(from, to)` that need to be removed
+    ///
+    /// Parallel edges are listed once for every occurrence.
+    pub edges: Vec<(usize, usize)>,
+    /// A topological ordering of the graph with all feedback edges removed
+    pub order: Vec<usize>,
+}
+
+impl IndexGraph {
+    /// Internal method that orders a strongly connected component so that few
+    /// edges go backwards
+    ///
+    /// Uses the heuristic by Eades, Lin and Smyth: sinks are repeatedly
+    /// removed and placed at the end of the ordering, sources are removed and
+    /// placed at the start, and if there are neither, the vertex with the
+    /// largest difference of out-degree and in-degree is placed at the start.
+    ///
+    /// `in_degree`, `out_degree` and `removed` are used as scratch space. Only
+    /// the entries of vertices in the component are used, so the same scratch
+    /// space can be passed for every component without resetting it.
+    fn eades_order_internal(
+        &self,
+        partition: &SccPartition,
+        id: usize,
+        in_degree: &mut [usize],
+        out_degree: &mut [usize],
+        removed: &mut [bool],
+    ) -> Vec<usize> {
+        let component = partition.component(id);
+        if component.len() == 1 {
+            return component.to_vec()
+        }
+
+        // degrees within the component, ignoring self-loops
+        let inner_edges = |edges: &[usize], idx: usize| edges.iter()
+            .filter(|&&next_idx| next_idx != idx && partition.component_of(next_idx) == id)
+            .count();
+
+        let mut sources = Vec::new();
+        let mut sinks = Vec::new();
+        let mut heap = BinaryHeap::new();
+
+        for &idx in component {
+            in_degree[idx] = inner_edges(&self.vertices[idx].in_edges, idx);
+            out_degree[idx] = inner_edges(&self.vertices[idx].out_edges, idx);
+            heap.push((out_degree[idx] as isize - in_degree[idx] as isize, Reverse(idx)));
+        }
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        while front.len() + back.len() < component.len() {
+            // vertices can become both a source and a sink, skip them if
+            // they were already placed
+            let idx = if let Some(idx) = sinks.pop() {
+                if removed[idx] {
+                    continue
+                }
+
+                back.push(idx);
+                idx
+            } else if let Some(idx) = sources.pop() {
+                if removed[idx] {
+                    continue
+                }
+
+                front.push(idx);
+                idx
+            } else {
+                // vertex with largest out-degree minus in-degree, skipping
+                // outdated heap entries
+                let (delta, Reverse(idx)) = heap.pop().expect("heap contains every remaining vertex");
+                if removed[idx] || delta != out_degree[idx] as isize - in_degree[idx] as isize {
+                    continue
+                }
+
+                front.push(idx);
+                idx
+            };
+
+            removed[idx] = true;
+
+            for &next_idx in &self.vertices[idx].out_edges {
+                if next_idx == idx || removed[next_idx] || partition.component_of(next_idx) != id {
+                    continue
+                }
+
+                in_degree[next_idx] -= 1;
+                if in_degree[next_idx] == 0 {
+                    sources.push(next_idx);
+                }
+                heap.push((out_degree[next_idx] as isize - in_degree[next_idx] as isize, Reverse(next_idx)));
+            }
+
+            for &prev_idx in &self.vertices[idx].in_edges {
+                if prev_idx == idx || removed[prev_idx] || partition.component_of(prev_idx) != id {
+                    continue
+                }
+
+                out_degree[prev_idx] -= 1;
+                if out_degree[prev_idx] == 0 {
+                    sinks.push(prev_idx);
+                }
+                heap.push((out_degree[prev_idx] as isize - in_degree[prev_idx] as isize, Reverse(prev_idx)));
+            }
+        }
+
+        back.reverse();
+        front.extend(back);
+        front
+    }
+
+    /// Find a small set of edges whose removal makes the graph acyclic
+    ///
+    /// Orders the vertices of every strongly connected component using the
+    /// heuristic by Eades, Lin and Smyth, and concatenates these orderings in
+    /// topological order of the components. Every edge that goes backwards in
+    /// the resulting ordering, including every self-loop, is part of the
+    /// feedback arc set.
+    ///
+    /// The returned set is not guaranteed to be minimal, since finding a
+    /// minimum feedback arc set is NP-hard. This method runs in
+    /// `O((V + E) log V)` time.
+    ///
+    /// If the graph contains no cycles, the returned set is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2],
+    ///     vec![0, 3],
+    ///     vec![3]
+    /// ]);
+    ///
+    /// let fas = g.feedback_arc_set();
+    /// assert_eq!(fas.edges, vec![(2, 0), (3, 3)]);
+    /// assert_eq!(fas.order, vec![0, 1, 2, 3]);
+    /// ```
+    pub fn feedback_arc_set(&self) -> FeedbackArcSet {
+        let partition = self.scc_partition();
+
+        let len = self.vertices.len();
+        let mut in_degree = vec![0; len];
+        let mut out_degree = vec![0; len];
+        let mut removed = vec![false; len];
+
+        let order: Vec<_> = (0..partition.len())
+            .flat_map(|id| self.eades_order_internal(&partition, id, &mut in_degree, &mut out_degree, &mut removed))
+            .collect();
+
+        let mut position = vec![0; len];
+        for (pos, &idx) in order.iter().enumerate() {
+            position[idx] = pos;
+        }
+
+        let mut edges = Vec::new();
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            for &next_idx in &vertex.out_edges {
+                if position[next_idx] <= position[idx] {
+                    edges.push((idx, next_idx));
+                }
+            }
+        }
+
+        FeedbackArcSet { edges, order }
+    }
+}
+
+impl IndexGraph {
+    /// Internal method that checks whether the subgraph induced by some
+    /// vertices is acyclic
+    ///
+    /// The subgraph consists of the vertices in `vertices` for which `keep` is
+    /// `true`. `keep` must be `false` for every vertex not in `vertices`.
+    ///
+    /// `in_degree` is used as scratch space.
+    fn is_acyclic_internal(&self, vertices: &[usize], keep: &[bool], in_degree: &mut [usize]) -> bool {
+        let mut stack = Vec::new();
+        let mut kept_len = 0;
+
+        // Kahn's algorithm for toposort, restricted to kept vertices
+        for &idx in vertices.iter().filter(|&&idx| keep[idx]) {
+            kept_len += 1;
+            in_degree[idx] = self.vertices[idx].in_edges.iter()
+                .filter(|&&prev_idx| keep[prev_idx])
+                .count();
+
+            if in_degree[idx] == 0 {
+                stack.push(idx);
+            }
+        }
+
+        let mut sorted_len = 0;
+        while let Some(idx) = stack.pop() {
+            sorted_len += 1;
+
+            for &next_idx in &self.vertices[idx].out_edges {
+                if keep[next_idx] {
+                    in_degree[next_idx] -= 1;
+                    if in_degree[next_idx] == 0 {
+                        stack.push(next_idx);
+                    }
+                }
+            }
+        }
+
+        sorted_len == kept_len
+    }
+
+    /// Internal method that returns a copy of the graph without the edges of
+    /// removed vertices
+    fn without_vertices_internal(&self, removed: &[bool]) -> IndexGraph {
+        let mut graph = IndexGraph::with_vertices(self.vertices.len());
+
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            if removed[idx] {
+                continue
+            }
+
+            for &next_idx in &vertex.out_edges {
+                if !removed[next_idx] {
+                    graph.add_edge(idx, next_idx);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Find a small set of vertices whose removal makes the graph acyclic
+    ///
+    /// Repeatedly finds the strongly connected components of the remaining
+    /// graph and removes one vertex from every component that contains a
+    /// cycle, preferring vertices with self-loops and otherwise the vertex
+    /// with the largest product of in-degree and out-degree within its
+    /// component. Afterwards, every removed vertex that is not needed to break
+    /// all cycles is added back, so that no vertex can be dropped from the
+    /// returned set.
+    ///
+    /// The returned set is not guaranteed to be minimum, since finding a
+    /// minimum feedback vertex set is NP-hard. For an exact solution on small
+    /// graphs, see `IndexGraph::minimum_feedback_vertex_set()`.
+    ///
+    /// Returns the vertices of the set in increasing order. If the graph
+    /// contains no cycles, the returned set is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2, 3],
+    ///     vec![1],
+    ///     vec![0, 4],
+    ///     vec![4]
+    /// ]);
+    ///
+    /// assert_eq!(g.feedback_vertex_set(), vec![1, 4]);
+    /// ```
+    pub fn feedback_vertex_set(&self) -> Vec<usize> {
+        let len = self.vertices.len();
+        let mut removed = vec![false; len];
+        let mut set = Vec::new();
+
+        loop {
+            let partition = self.without_vertices_internal(&removed).scc_partition();
+            let round_start = set.len();
+
+            for id in (0..partition.len()).filter(|&id| partition.is_cyclic(id)) {
+                let inner_edges = |edges: &[usize]| edges.iter()
+                    .filter(|&&next_idx| !removed[next_idx] && partition.component_of(next_idx) == id)
+                    .count();
+
+                let best_idx = partition.component(id).iter()
+                    .map(|&idx| {
+                        let vertex = &self.vertices[idx];
+                        let self_loop = vertex.out_edges.contains(&idx);
+                        let weight = inner_edges(&vertex.in_edges) * inner_edges(&vertex.out_edges);
+                        (self_loop, weight, Reverse(idx))
+                    })
+                    .max()
+                    .map(|(_, _, Reverse(idx))| idx)
+                    .expect("components are never empty");
+
+                set.push(best_idx);
+            }
+
+            if set.len() == round_start {
+                break
+            }
+
+            for &idx in &set[round_start..] {
+                removed[idx] = true;
+            }
+        }
+
+        // add back vertices that are not needed, in reverse order of removal
+        let vertices: Vec<_> = (0..len).collect();
+        let mut keep: Vec<_> = removed.iter().map(|&removed| !removed).collect();
+        let mut in_degree = vec![0; len];
+
+        for pos in (0..set.len()).rev() {
+            let idx = set[pos];
+            keep[idx] = true;
+
+            if self.is_acyclic_internal(&vertices, &keep, &mut in_degree) {
+                set.swap_remove(pos);
+            } else {
+                keep[idx] = false;
+            }
+        }
+
+        set.sort_unstable();
+        set
+    }
+
+    /// Find a minimum set of vertices whose removal makes the graph acyclic
+    ///
+    /// Finds a smallest feedback vertex set of every strongly connected
+    /// component by trying all subsets of its vertices in order of increasing
+    /// size, and returns the union of these sets as `Some(set)`. This takes
+    /// exponential time in the size of the largest component.
+    ///
+    /// If any strongly connected component that contains a cycle has more than
+    /// `max_component_len` vertices, returns `None`.
+    ///
+    /// Returns the vertices of the set in increasing order. If the graph
+    /// contains no cycles, the returned set is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2, 3],
+    ///     vec![1],
+    ///     vec![0, 4],
+    ///     vec![4]
+    /// ]);
+    ///
+    /// assert_eq!(g.minimum_feedback_vertex_set(8), Some(vec![1, 4]));
+    /// assert_eq!(g.minimum_feedback_vertex_set(3), None);
+    /// ```
+    pub fn minimum_feedback_vertex_set(&self, max_component_len: usize) -> Option<Vec<usize>> {
+        let partition = self.scc_partition();
+        let cyclic_ids: Vec<_> = (0..partition.len())
+            .filter(|&id| partition.is_cyclic(id))
+            .collect();
+
+        if cyclic_ids.iter().any(|&id| partition.component_size(id) > max_component_len) {
+            return None
+        }
+
+        let len = self.vertices.len();
+        let mut keep = vec![false; len];
+        let mut in_degree = vec![0; len];
+        let mut set = Vec::new();
+
+        for id in cyclic_ids {
+            let component = partition.component(id);
+            for &idx in component {
+                keep[idx] = true;
+            }
+
+            // try all subsets of size 1, 2, ... as combinations of positions
+            // in the component, until one breaks all cycles
+            'search: for size in 1..=component.len() {
+                let mut positions: Vec<_> = (0..size).collect();
+
+                loop {
+                    for &pos in &positions {
+                        keep[component[pos]] = false;
+                    }
+
+                    let acyclic = self.is_acyclic_internal(component, &keep, &mut in_degree);
+
+                    for &pos in &positions {
+                        keep[component[pos]] = true;
+                    }
+
+                    if acyclic {
+                        set.extend(positions.iter().map(|&pos| component[pos]));
+                        break 'search
+                    }
+
+                    // advance to the next combination
+                    let mut i = size;
+                    while i > 0 && positions[i - 1] == component.len() - size + i - 1 {
+                        i -= 1;
+                    }
+                    if i == 0 {
+                        break
+                    }
+
+                    positions[i - 1] += 1;
+                    for j in i..size {
+                        positions[j] = positions[j - 1] + 1;
+                    }
+                }
+            }
+
+            for &idx in component {
+                keep[idx] = false;
+            }
+        }
+
+        set.sort_unstable();
+        Some(set)
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use super::Condensation;
+use super::IndexGraph;
+
+/// A compact reachability index of an `IndexGraph`
+///
+/// Stores a small number of interval labels for every strongly connected
+/// component, computed by randomized depth-first searches of the
+/// condensation (GRAIL). If a vertex reaches another, the labels of the
+/// second vertex are contained in the labels of the first, so most negative
+/// queries are answered in constant time. Remaining queries are answered by a
+/// depth-first search that skips every component whose labels rule it out.
+///
+/// See `IndexGraph::reachability_index()` for usage examples
+#[derive(Debug, Clone)]
+pub struct ReachabilityIndex {
+    condensation: Condensation,
+    num_labels: usize,
+    labels: Vec<(usize, usize)>,
+}
+
+impl ReachabilityIndex {
+    /// Returns a reference to the condensation of the graph the labels were
+    /// computed on
+    pub fn condensation(&self) -> &Condensation {
+        &self.condensation
+    }
+
+    /// Returns `true` if the labels of component `to_id` are contained in the
+    /// labels of component `from_id`
+    fn may_reach(&self, from_id: usize, to_id: usize) -> bool {
+        // components are numbered in topological order
+        if from_id > to_id {
+            return false
+        }
+
+        let from = &self.labels[from_id * self.num_labels..][..self.num_labels];
+        let to = &self.labels[to_id * self.num_labels..][..self.num_labels];
+
+        from.iter().zip(to).all(|(&(from_low, from_rank), &(to_low, to_rank))| {
+            from_low <= to_low && to_rank <= from_rank
+        })
+    }
+
+    /// Returns `true` if there is a path with at least one edge from `from`
+    /// to `to`
+    ///
+    /// A vertex only reaches itself if it is part of a cycle.
+    pub fn reaches(&self, from: usize, to: usize) -> bool {
+        let from_id = self.condensation.component_of(from);
+        let to_id = self.condensation.component_of(to);
+
+        if from_id == to_id {
+            return self.condensation.partition().is_cyclic(from_id)
+        }
+
+        if !self.may_reach(from_id, to_id) {
+            return false
+        }
+
+        // depth-first search, skipping components that can't reach the target
+        let components = self.condensation.graph();
+        let mut visited = HashSet::new();
+        let mut stack = vec![from_id];
+
+        while let Some(id) = stack.pop() {
+            for &next_id in &components[id].out_edges {
+                if next_id == to_id {
+                    return true
+                }
+
+                if self.may_reach(next_id, to_id) && visited.insert(next_id) {
+                    stack.push(next_id);
+                }
+            }
+        }
+
+        false
+    }
+}
+
+/// Mixes a vertex index and a label number into a pseudo-random number
+fn mix(idx: usize, label: usize) -> usize {
+    // splitmix64 finalizer
+    let mut x = (idx as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ label as u64;
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (x ^ (x >> 31)) as usize
+}
+
+impl IndexGraph {
+    /// Build a compact reachability index of the graph
+    ///
+    /// Computes `num_labels` interval labels for every strongly connected
+    /// component of the graph. Every label is computed by a depth-first search
+    /// of the condensation that visits children in a different pseudo-random
+    /// order, and consists of the post-order rank of a component and the
+    /// smallest rank of any component reachable from it.
+    ///
+    /// Building the index takes `O(num_labels * (V + E))` time, and the index
+    /// uses `O(V + E + num_labels * C)` space, where `C` is the number of
+    /// strongly connected components. Unlike `.transitive_closure()`, this
+    /// does not grow quadratically with the size of the graph. Queries that
+    /// can't be answered by the labels alone fall back to a pruned depth-first
+    /// search, so more labels make queries faster at the cost of memory. A
+    /// small number of labels, such as 2 to 5, is usually enough.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1, 2],
+    ///     vec![3],
+    ///     vec![3, 4],
+    ///     vec![],
+    ///     vec![2],
+    ///     vec![4]
+    /// ]);
+    ///
+    /// let index = g.reachability_index(2);
+    /// let closure = g.transitive_closure();
+    ///
+    /// assert!(index.reaches(0, 3));
+    /// assert!(index.reaches(4, 4));
+    /// assert!(!index.reaches(1, 2));
+    ///
+    /// for from in 0..6 {
+    ///     for to in 0..6 {
+    ///         assert_eq!(index.reaches(from, to), closure.reaches(from, to));
+    ///     }
+    /// }
+    /// ```
+    pub fn reachability_index(&self, num_labels: usize) -> ReachabilityIndex {
+        let condensation = self.condensation();
+        let components = condensation.graph();
+        let len = condensation.len();
+
+        let roots: Vec<_> = (0..len)
+            .filter(|&id| components[id].in_edges.is_empty())
+            .collect();
+
+        let mut labels = vec![(0, 0); len * num_labels];
+        let mut visited = vec![false; len];
+        let mut dfs_stack = Vec::new();
+
+        for label in 0..num_labels {
+            visited.iter_mut().for_each(|visited| *visited = false);
+            let mut rank = 0;
+
+            // start a depth-first search from every root, in a rotated order
+            let offset = if roots.is_empty() { 0 } else { mix(len, label) % roots.len() };
+            for root_pos in 0..roots.len() {
+                let root_id = roots[(offset + root_pos) % roots.len()];
+
+                visited[root_id] = true;
+                dfs_stack.push((root_id, 0));
+
+                while let Some((id, edge_pos)) = dfs_stack.pop() {
+                    let out_edges = &components[id].out_edges;
+
+                    if edge_pos < out_edges.len() {
+                        dfs_stack.push((id, edge_pos + 1));
+
+                        // visit children in a rotated order
+                        let edge_idx = (mix(id, label) % out_edges.len() + edge_pos) % out_edges.len();
+                        let next_id = out_edges[edge_idx];
+                        if !visited[next_id] {
+                            visited[next_id] = true;
+                            dfs_stack.push((next_id, 0));
+                        }
+                    } else {
+                        // all children are finished, assign post-order rank
+                        let low = out_edges.iter()
+                            .map(|&next_id| labels[next_id * num_labels + label].0)
+                            .fold(rank, usize::min);
+
+                        labels[id * num_labels + label] = (low, rank);
+                        rank += 1;
+                    }
+                }
+            }
+        }
+
+        ReachabilityIndex { condensation, num_labels, labels }
+    }
+}
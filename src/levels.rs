@@ -0,0 +1,91 @@
+use std::mem;
+
+use super::IndexGraph;
+
+impl IndexGraph {
+    /// Perform topological sort on the graph, grouping vertices into levels
+    ///
+    /// If the graph contains no cycles, finds the topological ordering of this
+    /// graph using Kahn's algorithm and returns it as `Some(levels)`, where
+    /// every level contains the vertices whose incoming edges all come from
+    /// vertices in earlier levels. The first level contains all vertices with
+    /// in-degree zero. Vertices in the same level do not depend on each other,
+    /// so every level can be processed in parallel.
+    ///
+    /// Concatenating all levels results in the same ordering as returned by
+    /// `.toposort()`.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// This example creates an `IndexGraph` of the example graph from the
+    /// Wikipedia page for
+    /// [Topological sorting](https://en.wikipedia.org/wiki/Topological_sorting)
+    /// and groups its topological ordering into levels.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![3],
+    ///     vec![3, 4],
+    ///     vec![4, 7],
+    ///     vec![5, 6, 7],
+    ///     vec![6],
+    ///     vec![],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let mut g2 = g.clone();
+    /// g2.add_edge(6, 2); // cycle [2, 4, 6]
+    ///
+    /// assert_eq!(g.toposort_levels(), Some(vec![
+    ///     vec![0, 1, 2],
+    ///     vec![3, 4],
+    ///     vec![5, 7, 6]
+    /// ]));
+    /// assert_eq!(g2.toposort_levels(), None);
+    /// ```
+    pub fn toposort_levels(mut self) -> Option<Vec<Vec<usize>>> {
+        let mut levels = Vec::new();
+        let mut current = Vec::new();
+        let mut next = Vec::new();
+        let mut sorted_len = 0;
+
+        // Kahn's algorithm for toposort, processing one level at a time
+
+        // first level contains vertices with in-degree zero
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            if vertex.in_degree == 0 {
+                current.push(idx);
+            }
+        }
+
+        // decrement in-degree of neighboring edges of the current level
+        // add to next level if in-degree zero
+        while !current.is_empty() {
+            for &idx in &current {
+                for edge_idx in 0..self.vertices[idx].out_edges.len() {
+                    let next_idx = self.vertices[idx].out_edges[edge_idx];
+
+                    self.vertices[next_idx].in_degree -= 1;
+                    if self.vertices[next_idx].in_degree == 0 {
+                        next.push(next_idx);
+                    }
+                }
+            }
+
+            sorted_len += current.len();
+            levels.push(mem::replace(&mut current, mem::take(&mut next)));
+        }
+
+        // if every vertex appears in a level, sort is successful
+        if sorted_len == self.vertices.len() {
+            Some(levels)
+        } else {
+            None
+        }
+    }
+}
@@ -21,6 +21,56 @@
 //!   (Kosaraju's algorithm)
 //! - both algorithms are available either as single methods (`.toposort()` and
 //!   `.scc()`) or as a combined method (`.toposort_or_scc()`) on `IndexGraph`
+//! - a complete partition of the graph into strongly connected components,
+//!   including trivial ones, is available via `.scc_partition()`
+//! - the condensation of a graph, which collapses every strongly connected
+//!   component into a single vertex, can be built with `.condensation()`
+//! - an alternative single-pass algorithm for strongly connected components
+//!   (Tarjan's algorithm) is available via `.scc_tarjan()`
+//! - a canonical topological ordering that always emits the smallest ready
+//!   vertex first is available via `.toposort_lexical()`
+//! - the choice of the next ready vertex can be customized with
+//!   `.toposort_by()` and `.toposort_by_key()`
+//! - a topological ordering grouped into levels of vertices that can be
+//!   processed in parallel is available via `.toposort_levels()`
+//! - all topological orderings of a graph can be enumerated lazily with
+//!   `.all_toposorts()` and counted with `.count_toposorts()`
+//! - externally supplied orderings can be validated with `.check_order()`
+//! - a concrete cycle can be found for diagnostics with `.find_cycle()`, and
+//!   all elementary cycles can be enumerated with `.elementary_cycles()`
+//!   (Johnson's algorithm)
+//! - shortest cycles through a vertex or within every strongly connected
+//!   component can be found with `.shortest_cycle_through()` and
+//!   `.shortest_cycles()`
+//! - a small set of edges whose removal makes a graph acyclic can be found
+//!   with `.feedback_arc_set()` (Eades-Lin-Smyth heuristic)
+//! - a small set of vertices whose removal makes a graph acyclic can be found
+//!   with `.feedback_vertex_set()`, or exactly for small graphs with
+//!   `.minimum_feedback_vertex_set()`
+//! - graphs with cycles can be sorted as far as possible with
+//!   `.toposort_partial()`, which reports cycles and blocked vertices separately
+//! - a total ordering that places cycles contiguously is available via
+//!   `.toposort_lenient()`
+//! - only the part of a graph reachable from a set of targets can be sorted
+//!   with `.toposort_from()`
+//! - redundant edges can be removed with `.transitive_reduction()`, which also
+//!   finds a minimal equivalent graph for graphs with cycles
+//! - reachability queries can be answered in constant time using the bit
+//!   vector based index returned by `.transitive_closure()`
+//! - for very large graphs, `.reachability_index()` builds a compact interval
+//!   labelling index (GRAIL) that answers reachability queries with much less
+//!   memory
+//! - the critical path and the earliest start, latest start and slack of every
+//!   vertex with a duration can be computed with `.critical_path()`
+//! - single-source shortest and longest paths with edge weights can be found
+//!   on acyclic graphs with `.shortest_paths()` and `.longest_paths()`
+//! - dominator trees and dominance frontiers of control flow graphs can be
+//!   computed with `.dominators()` (Cooper-Harvey-Kennedy algorithm)
+//! - the loop nesting forest of a control flow graph, including irreducible
+//!   loops, can be computed with `.loop_forest()`
+//! - depth-first search from a list of roots with pre-order, post-order and
+//!   reverse post-order numbering and edge classification is available via
+//!   `.dfs()`
 //!
 //! The `id-arena` feature adds an additional wrapper type (`ArenaGraph`) that
 //! allows topological sorting and finding of strongly connected components on
@@ -65,6 +115,44 @@ use std::slice::Iter as SliceIter;
 use std::ops::Index;
 use std::mem;
 
+mod partition;
+mod condensation;
+mod tarjan;
+mod priority;
+mod levels;
+mod linear_extensions;
+mod check;
+mod cycles;
+mod feedback;
+mod partial;
+mod reach;
+mod reduction;
+mod closure;
+mod grail;
+mod schedule;
+mod paths;
+mod dominators;
+mod loops;
+mod dfs;
+mod bitset;
+
+pub use partition::*;
+pub use condensation::*;
+pub use linear_extensions::*;
+pub use check::*;
+pub use cycles::*;
+pub use feedback::*;
+pub use partial::*;
+pub use reach::*;
+pub use reduction::*;
+pub use closure::*;
+pub use grail::*;
+pub use schedule::*;
+pub use paths::*;
+pub use dominators::*;
+pub use loops::*;
+pub use dfs::*;
+
 #[cfg(feature = "id-arena")]
 mod arena_graph;
 
@@ -282,14 +370,15 @@ impl IndexGraph {
     /// If the graph contains no cycles, finds the topological ordering of this
     /// graph using Kahn's algorithm and returns it as `Ok(sorted)`.
     ///
-    /// If the graph contains cycles, returns the graph as Err(self).
+    /// If the graph contains cycles, returns the graph together with the
+    /// vertices that could be sorted as `Err((self, sorted))`.
     ///
     /// This method is not public because it breaks the invariants of
     /// `Vertex.in_degree` and `Vertex.out_degree`
     ///
     /// This method sets `Vertex.out_degree` to zero for every vertex so that
     /// the precondition of `IndexGraph::scc_internal()` is fulfilled
-    fn try_toposort_internal(mut self) -> Result<Vec<usize>, IndexGraph> {
+    fn try_toposort_internal(mut self) -> Result<Vec<usize>, (IndexGraph, Vec<usize>)> {
         let mut queue = Queue::new();
         let mut sorted = Vec::new();
 
@@ -327,7 +416,7 @@ impl IndexGraph {
         if sorted.len() == self.vertices.len() {
             Ok(sorted)
         } else {
-            Err(self)
+            Err((self, sorted))
         }
     }
 
@@ -341,7 +430,7 @@ impl IndexGraph {
     /// For examples, see `IndexGraph::toposort()`
     pub fn try_toposort(self) -> Result<Vec<usize>, IndexGraph> {
         self.try_toposort_internal()
-            .map_err(|mut graph| {
+            .map_err(|(mut graph, _)| {
                 for vertex in graph.vertices.iter_mut() {
                     vertex.in_degree = vertex.in_edges.len();
                     vertex.out_degree = vertex.out_edges.len();
@@ -409,23 +498,31 @@ impl IndexGraph {
 
         // Kosaraju's algorithm for strongly connected components
 
-        // start depth-first search with first vertex
+        // start depth-first search with every vertex not yet visited
         let mut queue = Queue::new();
-        let mut dfs_stack = vec![(0, 0)];
-        self.vertices[0].out_degree = 1;
+        let mut dfs_stack = Vec::new();
 
-        // add vertices to queue in post-order
-        while let Some((idx, edge_idx)) = dfs_stack.pop() {
-            if edge_idx < self.vertices[idx].out_edges.len() {
-                dfs_stack.push((idx, edge_idx + 1));
+        for root_idx in 0..self.vertices.len() {
+            if self.vertices[root_idx].out_degree != 0 {
+                continue
+            }
 
-                let next_idx = self.vertices[idx].out_edges[edge_idx];
-                if self.vertices[next_idx].out_degree == 0 {
-                    self.vertices[next_idx].out_degree = 1;
-                    dfs_stack.push((next_idx, 0));
+            self.vertices[root_idx].out_degree = 1;
+            dfs_stack.push((root_idx, 0));
+
+            // add vertices to queue in post-order
+            while let Some((idx, edge_idx)) = dfs_stack.pop() {
+                if edge_idx < self.vertices[idx].out_edges.len() {
+                    dfs_stack.push((idx, edge_idx + 1));
+
+                    let next_idx = self.vertices[idx].out_edges[edge_idx];
+                    if self.vertices[next_idx].out_degree == 0 {
+                        self.vertices[next_idx].out_degree = 1;
+                        dfs_stack.push((next_idx, 0));
+                    }
+                } else {
+                    queue.push_back(idx);
                 }
-            } else {
-                queue.push_back(idx);
             }
         }
 
@@ -493,6 +590,16 @@ impl IndexGraph {
     ///
     /// assert_eq!(g.scc(), vec![vec![4, 1, 0], vec![3, 2, 7], vec![5, 6]]);
     /// ```
+    ///
+    /// Cycles are found even if they are not reachable from the first vertex.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![vec![], vec![2], vec![1]]);
+    ///
+    /// assert_eq!(g.scc(), vec![vec![2, 1]]);
+    /// ```
     pub fn scc(mut self) -> Vec<Vec<usize>> {
         for vertex in self.vertices.iter_mut() {
             vertex.out_degree = 0;
@@ -543,7 +650,7 @@ impl IndexGraph {
     pub fn toposort_or_scc(self) -> Result<Vec<usize>, Vec<Vec<usize>>> {
         match self.try_toposort_internal() {
             Ok(sorted) => Ok(sorted),
-            Err(graph) => Err(graph.scc_internal())
+            Err((graph, _)) => Err(graph.scc_internal())
         }
     }
 }
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::bitset::BitSet;
+use super::IndexGraph;
+
+/// An error returned by `IndexGraph::count_toposorts()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountError {
+    /// The number of topological orderings does not fit into a `u128`
+    Overflow,
+    /// Counting the topological orderings would need more intermediate states
+    /// than the given limit
+    LimitExceeded,
+}
+
+impl fmt::Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CountError::Overflow => write!(f, "number of topological orderings overflows u128"),
+            CountError::LimitExceeded => write!(f, "state limit exceeded while counting topological orderings"),
+        }
+    }
+}
+
+impl Error for CountError {}
+
+/// An iterator over all topological orderings of an `IndexGraph`
+///
+/// Created by `IndexGraph::all_toposorts()`. Orderings are produced lazily by
+/// backtracking over the in-degrees of the vertices, in lexicographical order.
+#[derive(Debug, Clone)]
+pub struct AllToposorts<'g> {
+    graph: &'g IndexGraph,
+    in_degree: Vec<usize>,
+    used: Vec<bool>,
+    order: Vec<usize>,
+    cursor: Vec<usize>,
+    done: bool,
+}
+
+impl<'g> AllToposorts<'g> {
+    fn new(graph: &'g IndexGraph) -> Self {
+        let len = graph.vertices.len();
+
+        AllToposorts {
+            graph,
+            in_degree: graph.vertices.iter().map(|vertex| vertex.in_degree).collect(),
+            used: vec![false; len],
+            order: Vec::with_capacity(len),
+            cursor: vec![0; len + 1],
+            // a graph with cycles has no topological orderings
+            done: graph.clone().toposort().is_none(),
+        }
+    }
+
+    /// Append a vertex to the current ordering
+    fn push(&mut self, idx: usize) {
+        self.used[idx] = true;
+        self.order.push(idx);
+
+        for &next_idx in &self.graph.vertices[idx].out_edges {
+            self.in_degree[next_idx] -= 1;
+        }
+    }
+
+    /// Remove the last vertex from the current ordering
+    fn pop(&mut self) -> Option<usize> {
+        let idx = self.order.pop()?;
+        self.used[idx] = false;
+
+        for &next_idx in &self.graph.vertices[idx].out_edges {
+            self.in_degree[next_idx] += 1;
+        }
+
+        Some(idx)
+    }
+}
+
+impl Iterator for AllToposorts<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let len = self.used.len();
+
+        while !self.done {
+            let depth = self.order.len();
+
+            // ordering is complete, return it and backtrack one step
+            if depth == len {
+                let sorted = self.order.clone();
+                if self.pop().is_none() {
+                    self.done = true;
+                }
+
+                return Some(sorted)
+            }
+
+            // find the next vertex with in-degree zero that was not yet tried
+            // at this depth
+            let candidate = (self.cursor[depth]..len)
+                .find(|&idx| !self.used[idx] && self.in_degree[idx] == 0);
+
+            match candidate {
+                Some(idx) => {
+                    self.cursor[depth] = idx + 1;
+                    self.cursor[depth + 1] = 0;
+                    self.push(idx);
+                }
+                None => {
+                    if self.pop().is_none() {
+                        self.done = true;
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl FusedIterator for AllToposorts<'_> {}
+
+impl IndexGraph {
+    /// Returns an iterator over all topological orderings of the graph
+    ///
+    /// Every valid topological ordering of the graph is produced exactly once,
+    /// in lexicographical order. Orderings are computed lazily, so the number
+    /// of orderings can be limited with `.take()`.
+    ///
+    /// If the graph contains cycles, the iterator is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![2],
+    ///     vec![2],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let mut g2 = g.clone();
+    /// g2.add_edge(2, 0);
+    ///
+    /// assert_eq!(g.all_toposorts().take(4).collect::<Vec<_>>(), vec![
+    ///     vec![0, 1, 2, 3],
+    ///     vec![0, 1, 3, 2],
+    ///     vec![0, 3, 1, 2],
+    ///     vec![1, 0, 2, 3]
+    /// ]);
+    /// assert_eq!(g.all_toposorts().count(), 8);
+    /// assert_eq!(g2.all_toposorts().count(), 0);
+    /// ```
+    pub fn all_toposorts(&self) -> AllToposorts<'_> {
+        AllToposorts::new(self)
+    }
+
+    /// Count the topological orderings of the graph
+    ///
+    /// Counts the number of valid topological orderings (linear extensions)
+    /// of the graph without enumerating them. The graph is split into weakly
+    /// connected components, the orderings of every component are counted
+    /// with dynamic programming over the sets of vertices that can form a
+    /// prefix of an ordering, and the counts are combined.
+    ///
+    /// The number of prefix sets of a given size grows exponentially with the
+    /// width of the graph, but stays small for narrow graphs. If more than
+    /// `limit` prefix sets of the same size are needed for a component,
+    /// `Err(CountError::LimitExceeded)` is returned. If the result does not
+    /// fit into a `u128`, `Err(CountError::Overflow)` is returned.
+    ///
+    /// If the graph contains cycles, returns `Ok(0)`, regardless of `limit`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::{CountError, IndexGraph};
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![2],
+    ///     vec![2],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let mut g2 = g.clone();
+    /// g2.add_edge(2, 0);
+    ///
+    /// assert_eq!(g.count_toposorts(1000), Ok(8));
+    /// assert_eq!(g2.count_toposorts(1000), Ok(0));
+    ///
+    /// // 30 unconnected vertices have 30! orderings, 35! overflows
+    /// let factorial_30 = (1..=30).product::<u128>();
+    /// assert_eq!(IndexGraph::with_vertices(30).count_toposorts(1000), Ok(factorial_30));
+    /// assert_eq!(IndexGraph::with_vertices(35).count_toposorts(1000), Err(CountError::Overflow));
+    ///
+    /// // 8 parallel chains of 3 vertices need too many prefix sets
+    /// let mut wide = IndexGraph::with_vertices(27);
+    /// for chain in 0..8 {
+    ///     let start = 1 + chain * 3;
+    ///     wide.add_edge(0, start);
+    ///     wide.add_edge(start, start + 1);
+    ///     wide.add_edge(start + 1, start + 2);
+    /// }
+    /// assert_eq!(wide.count_toposorts(10), Err(CountError::LimitExceeded));
+    ///
+    /// // cycles are detected before any limit or overflow is reached
+    /// wide.add_edge(25, 26);
+    /// wide.add_edge(26, 25);
+    /// assert_eq!(wide.count_toposorts(10), Ok(0));
+    ///
+    /// let mut g3 = IndexGraph::with_vertices(37);
+    /// g3.add_edge(35, 36);
+    /// g3.add_edge(36, 35);
+    /// assert_eq!(g3.count_toposorts(1000), Ok(0));
+    /// ```
+    pub fn count_toposorts(&self, limit: usize) -> Result<u128, CountError> {
+        let len = self.vertices.len();
+
+        if self.clone().toposort().is_none() {
+            return Ok(0)
+        }
+
+        // index of every vertex in its weakly connected component
+        let mut local = vec![usize::MAX; len];
+        let mut total: u128 = 1;
+        let mut total_len = 0;
+
+        for root_idx in 0..len {
+            if local[root_idx] != usize::MAX {
+                continue
+            }
+
+            // collect weakly connected component by depth-first search in
+            // both edge directions
+            let mut component = vec![root_idx];
+            local[root_idx] = 0;
+
+            let mut pos = 0;
+            while pos < component.len() {
+                let idx = component[pos];
+                pos += 1;
+
+                let vertex = &self.vertices[idx];
+                for &next_idx in vertex.out_edges.iter().chain(&vertex.in_edges) {
+                    if local[next_idx] == usize::MAX {
+                        local[next_idx] = component.len();
+                        component.push(next_idx);
+                    }
+                }
+            }
+
+            let count = self.count_component_toposorts(&component, &local, limit)?;
+
+            // interleave orderings of this component with the previous ones
+            total_len += component.len();
+            total = binomial(total_len, component.len())
+                .and_then(|ways| ways.checked_mul(count))
+                .and_then(|ways| ways.checked_mul(total))
+                .ok_or(CountError::Overflow)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Internal method that counts the topological orderings of a weakly
+    /// connected component
+    ///
+    /// `local` maps every vertex of the component to its index in `component`.
+    fn count_component_toposorts(&self, component: &[usize], local: &[usize], limit: usize) -> Result<u128, CountError> {
+        // map from sets of vertices that can form a prefix of an ordering to
+        // the number of orderings of that prefix
+        let mut layer = HashMap::new();
+        layer.insert(BitSet::new(component.len()), 1u128);
+
+        for _ in 0..component.len() {
+            let mut next_layer: HashMap<BitSet, u128> = HashMap::new();
+
+            for (set, &count) in &layer {
+                for (local_idx, &idx) in component.iter().enumerate() {
+                    if set.contains(local_idx) {
+                        continue
+                    }
+
+                    let ready = self.vertices[idx].in_edges.iter()
+                        .all(|&prev_idx| set.contains(local[prev_idx]));
+                    if !ready {
+                        continue
+                    }
+
+                    let mut next_set = set.clone();
+                    next_set.insert(local_idx);
+
+                    let entry = next_layer.entry(next_set).or_insert(0);
+                    *entry = entry.checked_add(count).ok_or(CountError::Overflow)?;
+
+                    // stop as soon as the limit is exceeded, so that it also
+                    // bounds time and memory spent on the next layer
+                    if next_layer.len() > limit {
+                        return Err(CountError::LimitExceeded)
+                    }
+                }
+            }
+
+            layer = next_layer;
+        }
+
+        // only the set of all vertices remains
+        Ok(layer.values().sum())
+    }
+}
+
+/// Computes the binomial coefficient `n` choose `k`, or `None` on overflow
+fn binomial(n: usize, k: usize) -> Option<u128> {
+    let k = k.min(n - k) as u128;
+    let n = n as u128;
+    let mut result: u128 = 1;
+
+    for i in 1..=k {
+        // result * (n - k + i) is always divisible by i, divide by the common
+        // factor first to avoid spurious overflow
+        let factor = n - k + i;
+        let common = gcd(result, i);
+        result = (result / common).checked_mul(factor / (i / common))?;
+    }
+
+    Some(result)
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let rem = a % b;
+        a = b;
+        b = rem;
+    }
+
+    a
+}
@@ -0,0 +1,203 @@
+use super::IndexGraph;
+
+/// A loop in a loop nesting forest
+///
+/// See `IndexGraph::loop_forest()` for usage examples
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loop {
+    /// The vertices through which the loop can be entered, in increasing
+    /// order
+    ///
+    /// A reducible loop has exactly one header, which dominates its body.
+    pub headers: Vec<usize>,
+    /// All vertices of the loop, including the vertices of nested loops, in
+    /// increasing order
+    pub body: Vec<usize>,
+    /// The id of the innermost loop containing this loop
+    pub parent: Option<usize>,
+    /// The nesting depth of the loop, starting at 1 for outermost loops
+    pub depth: usize,
+    /// `true` if the loop can be entered through more than one vertex
+    pub irreducible: bool,
+}
+
+/// The loop nesting forest of an `IndexGraph` with an entry vertex
+///
+/// Loops are identified by their index, and every loop appears after the loop
+/// containing it.
+///
+/// See `IndexGraph::loop_forest()` for usage examples
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopForest {
+    loops: Vec<Loop>,
+    loop_of: Vec<Option<usize>>,
+}
+
+impl LoopForest {
+    /// Returns the list of loops
+    pub fn loops(&self) -> &[Loop] {
+        &self.loops
+    }
+
+    /// Returns the id of the innermost loop containing a vertex, or `None` if
+    /// the vertex is not part of a loop
+    pub fn innermost_loop(&self, vertex: usize) -> Option<usize> {
+        self.loop_of[vertex]
+    }
+
+    /// Returns the number of loops containing a vertex
+    pub fn loop_depth(&self, vertex: usize) -> usize {
+        self.loop_of[vertex].map_or(0, |id| self.loops[id].depth)
+    }
+
+    /// Returns `true` if the vertex is a header of a loop
+    pub fn is_header(&self, vertex: usize) -> bool {
+        match self.loop_of[vertex] {
+            Some(id) => self.loops[id].headers.contains(&vertex),
+            None => false
+        }
+    }
+}
+
+impl IndexGraph {
+    /// Compute the loop nesting forest of the graph
+    ///
+    /// Finds the loops of the part of the graph reachable from `entry`, using
+    /// Steensgaard's construction in the framework by Ramalingam: every
+    /// strongly connected component that contains a cycle is a loop, and the
+    /// vertices of a loop that have an incoming edge from outside of it (or
+    /// are the entry) are its headers. Nested loops are found by removing all
+    /// edges into the headers and searching the body of every loop for
+    /// strongly connected components again.
+    ///
+    /// For reducible control flow, this results in the usual natural loops.
+    /// Loops with more than one header are irreducible.
+    ///
+    /// # Example
+    ///
+    /// This example creates a control flow graph with two nested loops, and a
+    /// third loop that can be entered through two different vertices.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],    // entry
+    ///     vec![2],    // outer loop header
+    ///     vec![3],    // inner loop header
+    ///     vec![2, 4], // inner loop latch
+    ///     vec![1, 5], // outer loop latch
+    ///     vec![6, 7], // branch into irreducible loop
+    ///     vec![7],
+    ///     vec![6, 8],
+    ///     vec![]      // exit
+    /// ]);
+    ///
+    /// let forest = g.loop_forest(0);
+    /// let loops = forest.loops();
+    ///
+    /// assert_eq!(loops.len(), 3);
+    /// assert_eq!(loops[0].headers, vec![6, 7]);
+    /// assert!(loops[0].irreducible);
+    /// assert_eq!(loops[1].headers, vec![1]);
+    /// assert_eq!(loops[1].body, vec![1, 2, 3, 4]);
+    /// assert_eq!(loops[2].headers, vec![2]);
+    /// assert_eq!(loops[2].parent, Some(1));
+    ///
+    /// assert_eq!(forest.loop_depth(3), 2);
+    /// assert_eq!(forest.loop_depth(4), 1);
+    /// assert_eq!(forest.loop_depth(8), 0);
+    /// assert!(forest.is_header(2));
+    /// ```
+    pub fn loop_forest(&self, entry: usize) -> LoopForest {
+        let len = self.vertices.len();
+
+        // find vertices reachable from entry
+        let mut reachable = vec![false; len];
+        let mut stack = vec![entry];
+        reachable[entry] = true;
+
+        while let Some(idx) = stack.pop() {
+            for &next_idx in &self.vertices[idx].out_edges {
+                if !reachable[next_idx] {
+                    reachable[next_idx] = true;
+                    stack.push(next_idx);
+                }
+            }
+        }
+
+        let mut loops = Vec::new();
+        let mut loop_of = vec![None; len];
+        let mut header = vec![false; len];
+
+        let mut local = vec![usize::MAX; len];
+        let mut in_body = vec![false; len];
+
+        let all_vertices: Vec<_> = (0..len).filter(|&idx| reachable[idx]).collect();
+        let mut worklist = vec![(all_vertices, None, 1)];
+
+        while let Some((vertices, parent, depth)) = worklist.pop() {
+            // build subgraph of the vertices, without edges into headers
+            for (local_idx, &idx) in vertices.iter().enumerate() {
+                local[idx] = local_idx;
+            }
+
+            let mut sub = IndexGraph::with_vertices(vertices.len());
+            for (local_idx, &idx) in vertices.iter().enumerate() {
+                for &next_idx in &self.vertices[idx].out_edges {
+                    if local[next_idx] != usize::MAX && !header[next_idx] {
+                        sub.add_edge(local_idx, local[next_idx]);
+                    }
+                }
+            }
+
+            for &idx in &vertices {
+                local[idx] = usize::MAX;
+            }
+
+            // every strongly connected component with a cycle is a loop
+            for component in sub.scc_tarjan() {
+                let cyclic = component.len() > 1 || sub[component[0]].out_edges.contains(&component[0]);
+                if !cyclic {
+                    continue
+                }
+
+                let mut body: Vec<_> = component.iter().map(|&local_idx| vertices[local_idx]).collect();
+                body.sort_unstable();
+
+                for &idx in &body {
+                    in_body[idx] = true;
+                }
+
+                let headers: Vec<_> = body.iter()
+                    .copied()
+                    .filter(|&idx| idx == entry || self.vertices[idx].in_edges.iter()
+                        .any(|&prev_idx| reachable[prev_idx] && !in_body[prev_idx]))
+                    .collect();
+
+                for &idx in &body {
+                    in_body[idx] = false;
+                }
+
+                let id = loops.len();
+                for &idx in &body {
+                    loop_of[idx] = Some(id);
+                }
+                for &idx in &headers {
+                    header[idx] = true;
+                }
+
+                worklist.push((body.clone(), Some(id), depth + 1));
+                loops.push(Loop {
+                    irreducible: headers.len() > 1,
+                    headers,
+                    body,
+                    parent,
+                    depth,
+                });
+            }
+        }
+
+        LoopForest { loops, loop_of }
+    }
+}
@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+use super::IndexGraph;
+
+/// The result of a topological sort that continues past cycles
+///
+/// Returned by `IndexGraph::toposort_partial()`. Every vertex of the graph
+/// appears either in `sorted`, in one of the `cycles`, or in `blocked`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialToposort {
+    /// A topological ordering of all vertices that are not part of a cycle and
+    /// do not depend on one
+    pub sorted: Vec<usize>,
+    /// The strongly connected components that contain a cycle
+    pub cycles: Vec<Vec<usize>>,
+    /// Vertices that are not part of a cycle, but depend on one, in increasing
+    /// order
+    pub blocked: Vec<usize>,
+}
+
+impl PartialToposort {
+    /// Returns `true` if every vertex was sorted, i.e. the graph contains no
+    /// cycles
+    pub fn is_complete(&self) -> bool {
+        self.cycles.is_empty()
+    }
+}
+
+/// A total ordering of a graph that may contain cycles
+///
+/// Returned by `IndexGraph::toposort_lenient()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LenientToposort {
+    /// An ordering of all vertices of the graph
+    ///
+    /// Vertices of every strongly connected component appear contiguously,
+    /// and components appear in topological order.
+    pub order: Vec<usize>,
+    /// Ranges of `order` that contain the vertices of a strongly connected
+    /// component with a cycle, in increasing order
+    pub cyclic_runs: Vec<Range<usize>>,
+}
+
+impl LenientToposort {
+    /// Returns `true` if the ordering is a topological ordering, i.e. the
+    /// graph contains no cycles
+    pub fn is_acyclic(&self) -> bool {
+        self.cyclic_runs.is_empty()
+    }
+}
+
+impl IndexGraph {
+    /// Perform topological sort on as much of the graph as possible
+    ///
+    /// Finds a topological ordering of all vertices that can be reached
+    /// without passing through a cycle using Kahn's algorithm. If the graph
+    /// contains cycles, the strongly connected components that contain a
+    /// cycle are found using Kosaraju's algorithm, and the remaining vertices
+    /// that only depend on a cycle are returned separately.
+    ///
+    /// If the graph contains no cycles, `sorted` is the same ordering as
+    /// returned by `.toposort()`.
+    ///
+    /// # Example
+    ///
+    /// This example creates an `IndexGraph` of the example graph from the
+    /// Wikipedia page for
+    /// [Topological sorting](https://en.wikipedia.org/wiki/Topological_sorting)
+    /// with an additional edge that creates a cycle, and sorts everything that
+    /// is not blocked by it.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let mut g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![3],
+    ///     vec![3, 4],
+    ///     vec![4, 7],
+    ///     vec![5, 6, 7],
+    ///     vec![6],
+    ///     vec![],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    /// g.add_edge(6, 2); // cycle [2, 4, 6]
+    ///
+    /// let partial = g.toposort_partial();
+    /// assert!(!partial.is_complete());
+    /// assert_eq!(partial.sorted, vec![0, 1, 3, 5]);
+    /// assert_eq!(partial.cycles, vec![vec![4, 2, 6]]);
+    /// assert_eq!(partial.blocked, vec![7]);
+    /// ```
+    pub fn toposort_partial(self) -> PartialToposort {
+        let len = self.vertices.len();
+
+        let (graph, sorted) = match self.try_toposort_internal() {
+            Ok(sorted) => return PartialToposort { sorted, cycles: Vec::new(), blocked: Vec::new() },
+            Err(result) => result
+        };
+
+        let cycles = graph.scc_internal();
+
+        // every vertex that is neither sorted nor in a cycle is blocked
+        let mut done = vec![false; len];
+        for &idx in sorted.iter().chain(cycles.iter().flatten()) {
+            done[idx] = true;
+        }
+
+        let blocked = (0..len).filter(|&idx| !done[idx]).collect();
+
+        PartialToposort { sorted, cycles, blocked }
+    }
+
+    /// Find a total ordering of the graph, even if it contains cycles
+    ///
+    /// Sorts the condensation of the graph, and places the vertices of every
+    /// strongly connected component next to each other in increasing order.
+    /// Every edge that does not go forward in the resulting ordering is part
+    /// of a cycle, and the ranges of the ordering that contain cycles are
+    /// returned in `cyclic_runs`.
+    ///
+    /// If the graph contains no cycles, `order` is a topological ordering of
+    /// the graph.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![2],
+    ///     vec![0],
+    ///     vec![1, 3],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let lenient = g.toposort_lenient();
+    /// assert!(!lenient.is_acyclic());
+    /// assert_eq!(lenient.order, vec![0, 1, 2, 3]);
+    /// assert_eq!(lenient.cyclic_runs, vec![0..3]);
+    /// ```
+    pub fn toposort_lenient(&self) -> LenientToposort {
+        let partition = self.scc_partition();
+        let mut order = Vec::with_capacity(self.vertices.len());
+        let mut cyclic_runs = Vec::new();
+
+        for (id, component) in partition.components().iter().enumerate() {
+            let start = order.len();
+            order.extend_from_slice(component);
+            order[start..].sort_unstable();
+
+            if partition.is_cyclic(id) {
+                cyclic_runs.push(start..order.len());
+            }
+        }
+
+        LenientToposort { order, cyclic_runs }
+    }
+}
@@ -0,0 +1,190 @@
+use super::IndexGraph;
+
+/// A partition of the vertices of an `IndexGraph` into strongly connected
+/// components
+///
+/// Every vertex of the graph belongs to exactly one component, including
+/// vertices that are not part of any cycle (trivial components). Components
+/// are identified by their index, and are stored in topological order of the
+/// condensation of the graph, so every edge between two different components
+/// goes from a component with a lower index to one with a higher index.
+///
+/// See `IndexGraph::scc_partition()` for usage examples
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SccPartition {
+    components: Vec<Vec<usize>>,
+    component_of: Vec<usize>,
+    cyclic: Vec<bool>,
+}
+
+impl SccPartition {
+    /// Returns the number of components
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns `true` if there are no components, which is only the case for
+    /// an empty graph
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Returns the list of components in topological order
+    pub fn components(&self) -> &[Vec<usize>] {
+        &self.components
+    }
+
+    /// Returns the vertices of the component with the given id
+    pub fn component(&self, id: usize) -> &[usize] {
+        &self.components[id]
+    }
+
+    /// Returns the id of the component the given vertex belongs to
+    pub fn component_of(&self, vertex: usize) -> usize {
+        self.component_of[vertex]
+    }
+
+    /// Returns the number of vertices in the component with the given id
+    pub fn component_size(&self, id: usize) -> usize {
+        self.components[id].len()
+    }
+
+    /// Returns the number of vertices of every component, indexed by
+    /// component id
+    pub fn sizes(&self) -> Vec<usize> {
+        self.components.iter().map(Vec::len).collect()
+    }
+
+    /// Returns `true` if the component with the given id contains a cycle
+    ///
+    /// This is the case if the component has more than one vertex, or if its
+    /// only vertex has an edge to itself.
+    pub fn is_cyclic(&self, id: usize) -> bool {
+        self.cyclic[id]
+    }
+
+    /// Returns an iterator over the components that contain a cycle
+    pub fn cyclic_components(&self) -> impl Iterator<Item = &[usize]> + '_ {
+        self.components.iter()
+            .zip(&self.cyclic)
+            .filter(|&(_, &cyclic)| cyclic)
+            .map(|(component, _)| &component[..])
+    }
+
+    /// Returns the list of components in topological order
+    pub fn into_components(self) -> Vec<Vec<usize>> {
+        self.components
+    }
+}
+
+impl IndexGraph {
+    /// Partition the graph into strongly connected components
+    ///
+    /// Finds all strongly connected components of this graph using
+    /// Kosaraju's algorithm, starting a depth-first search from every vertex
+    /// that has not been visited yet. Unlike `.scc()`, the returned partition
+    /// also contains components consisting of a single vertex without a
+    /// self-loop, so that every vertex is assigned to a component.
+    ///
+    /// Components are returned in topological order of the condensation of
+    /// the graph.
+    ///
+    /// # Example
+    ///
+    /// This example creates a graph with two cycles, one of which is not
+    /// reachable from vertex 0, and finds its strongly connected components.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![0, 2],
+    ///     vec![],
+    ///     vec![4],
+    ///     vec![3, 2]
+    /// ]);
+    ///
+    /// let partition = g.scc_partition();
+    ///
+    /// assert_eq!(partition.components(), &[vec![3, 4], vec![0, 1], vec![2]]);
+    /// assert_eq!(partition.component_of(4), 0);
+    /// assert_eq!(partition.component_of(2), 2);
+    /// assert_eq!(partition.sizes(), vec![2, 2, 1]);
+    /// assert!(!partition.is_cyclic(2));
+    /// assert_eq!(partition.cyclic_components().count(), 2);
+    /// ```
+    pub fn scc_partition(&self) -> SccPartition {
+        let len = self.vertices.len();
+
+        // Kosaraju's algorithm for strongly connected components
+
+        // add vertices to postorder list, starting a depth-first search from
+        // every vertex not yet visited
+        let mut visited = vec![false; len];
+        let mut postorder = Vec::with_capacity(len);
+        let mut dfs_stack = Vec::new();
+
+        for root_idx in 0..len {
+            if visited[root_idx] {
+                continue
+            }
+
+            visited[root_idx] = true;
+            dfs_stack.push((root_idx, 0));
+
+            while let Some((idx, edge_idx)) = dfs_stack.pop() {
+                if edge_idx < self.vertices[idx].out_edges.len() {
+                    dfs_stack.push((idx, edge_idx + 1));
+
+                    let next_idx = self.vertices[idx].out_edges[edge_idx];
+                    if !visited[next_idx] {
+                        visited[next_idx] = true;
+                        dfs_stack.push((next_idx, 0));
+                    }
+                } else {
+                    postorder.push(idx);
+                }
+            }
+        }
+
+        // collect components by depth-first search in opposite edge direction
+        // from each vertex in reverse postorder
+        let mut component_of = vec![usize::MAX; len];
+        let mut components = Vec::new();
+        let mut cyclic = Vec::new();
+        let mut stack = Vec::new();
+
+        while let Some(root_idx) = postorder.pop() {
+            if component_of[root_idx] != usize::MAX {
+                continue
+            }
+
+            let id = components.len();
+            let mut component = vec![root_idx];
+            let mut self_loop = false;
+
+            component_of[root_idx] = id;
+            stack.push(root_idx);
+
+            while let Some(idx) = stack.pop() {
+                for &next_idx in &self.vertices[idx].in_edges {
+                    if next_idx == idx {
+                        self_loop = true;
+                    }
+
+                    if component_of[next_idx] == usize::MAX {
+                        component_of[next_idx] = id;
+                        component.push(next_idx);
+                        stack.push(next_idx);
+                    }
+                }
+            }
+
+            cyclic.push(self_loop || component.len() > 1);
+            components.push(component);
+        }
+
+        SccPartition { components, component_of, cyclic }
+    }
+}
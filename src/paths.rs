@@ -0,0 +1,173 @@
+use std::ops::Add;
+
+use super::IndexGraph;
+
+/// Single-source paths in an acyclic `IndexGraph`
+///
+/// Returned by `IndexGraph::shortest_paths()` and
+/// `IndexGraph::longest_paths()`. Stores the distance and predecessor of
+/// every vertex reachable from the source, forming a tree of paths from the
+/// source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DagPaths<T> {
+    source: usize,
+    distance: Vec<Option<T>>,
+    predecessor: Vec<Option<usize>>,
+}
+
+impl<T: Copy> DagPaths<T> {
+    /// Returns the source vertex of all paths
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Returns the length of the path from the source to a vertex, or `None`
+    /// if the vertex is not reachable from the source
+    pub fn distance(&self, vertex: usize) -> Option<T> {
+        self.distance[vertex]
+    }
+
+    /// Returns the vertex before the given vertex on its path from the
+    /// source, or `None` for the source and for unreachable vertices
+    pub fn predecessor(&self, vertex: usize) -> Option<usize> {
+        self.predecessor[vertex]
+    }
+
+    /// Returns the path from the source to a vertex, including both, or
+    /// `None` if the vertex is not reachable from the source
+    pub fn path_to(&self, vertex: usize) -> Option<Vec<usize>> {
+        self.distance[vertex]?;
+
+        let mut path = vec![vertex];
+        let mut cur_idx = vertex;
+        while let Some(prev_idx) = self.predecessor[cur_idx] {
+            path.push(prev_idx);
+            cur_idx = prev_idx;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl IndexGraph {
+    /// Internal method that finds paths from a source by relaxing edges in
+    /// topological order
+    ///
+    /// A path replaces the current path to a vertex if `better(new, current)`
+    /// returns `true`.
+    fn dag_paths_internal<T, F, B>(&self, source: usize, mut weight: F, better: B) -> Result<DagPaths<T>, Vec<Vec<usize>>>
+        where T: Copy + Default + Add<Output = T>, F: FnMut(usize, usize) -> T, B: Fn(T, T) -> bool
+    {
+        let sorted = self.clone().toposort_or_scc()?;
+        let len = self.vertices.len();
+
+        let mut distance = vec![None; len];
+        let mut predecessor = vec![None; len];
+        distance[source] = Some(T::default());
+
+        // vertices before the source in topological order are never reached
+        let start = sorted.iter().position(|&idx| idx == source).unwrap_or(len);
+        for &idx in &sorted[start..] {
+            let dist = match distance[idx] {
+                Some(dist) => dist,
+                None => continue
+            };
+
+            for &next_idx in &self.vertices[idx].out_edges {
+                let next_dist = dist + weight(idx, next_idx);
+
+                let improves = match distance[next_idx] {
+                    Some(cur_dist) => better(next_dist, cur_dist),
+                    None => true
+                };
+
+                if improves {
+                    distance[next_idx] = Some(next_dist);
+                    predecessor[next_idx] = Some(idx);
+                }
+            }
+        }
+
+        Ok(DagPaths { source, distance, predecessor })
+    }
+
+    /// Find shortest paths from a source vertex
+    ///
+    /// The weight of every edge is given by `weight(from, to)`, so parallel
+    /// edges always have the same weight. Weights may be negative.
+    /// `T::default()` is used as the length of the empty path.
+    ///
+    /// Relaxes the edges of every vertex in topological order, which runs in
+    /// `O(V + E)` time.
+    ///
+    /// If the graph contains cycles, finds the strongly connected components
+    /// of this graph using Kosaraju's algorithm and returns them as
+    /// `Err(cycles)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let weights = vec![vec![(1, 4), (2, 1)], vec![(3, 1)], vec![(1, 2), (3, 5)], vec![]];
+    /// let g = IndexGraph::from_adjacency_list(&weights.iter()
+    ///     .map(|edges| edges.iter().map(|&(to, _)| to).collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>());
+    /// let weight = |from: usize, to: usize| weights[from].iter()
+    ///     .find(|&&(next, _)| next == to)
+    ///     .unwrap().1;
+    ///
+    /// let paths = g.shortest_paths(0, weight).unwrap();
+    ///
+    /// assert_eq!(paths.distance(3), Some(4));
+    /// assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+    /// assert_eq!(g.shortest_paths(1, weight).unwrap().path_to(0), None);
+    /// ```
+    pub fn shortest_paths<T, F>(&self, source: usize, weight: F) -> Result<DagPaths<T>, Vec<Vec<usize>>>
+        where T: Copy + Default + PartialOrd + Add<Output = T>, F: FnMut(usize, usize) -> T
+    {
+        self.dag_paths_internal(source, weight, |new, cur| new < cur)
+    }
+
+    /// Find longest paths from a source vertex
+    ///
+    /// The weight of every edge is given by `weight(from, to)`, so parallel
+    /// edges always have the same weight. Weights may be negative.
+    /// `T::default()` is used as the length of the empty path.
+    ///
+    /// Relaxes the edges of every vertex in topological order, which runs in
+    /// `O(V + E)` time.
+    ///
+    /// If the graph contains cycles, finds the strongly connected components
+    /// of this graph using Kosaraju's algorithm and returns them as
+    /// `Err(cycles)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let weights = vec![vec![(1, 4), (2, 1)], vec![(3, 1)], vec![(1, 2), (3, 5)], vec![]];
+    /// let g = IndexGraph::from_adjacency_list(&weights.iter()
+    ///     .map(|edges| edges.iter().map(|&(to, _)| to).collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>());
+    /// let weight = |from: usize, to: usize| weights[from].iter()
+    ///     .find(|&&(next, _)| next == to)
+    ///     .unwrap().1;
+    ///
+    /// let paths = g.longest_paths(0, weight).unwrap();
+    ///
+    /// assert_eq!(paths.distance(3), Some(6));
+    /// assert_eq!(paths.path_to(3), Some(vec![0, 2, 3]));
+    ///
+    /// let mut g2 = g.clone();
+    /// g2.add_edge(3, 2); // cycles [2, 3] and [2, 1, 3]
+    /// assert_eq!(g2.longest_paths(0, |_, _| 1).unwrap_err(), vec![vec![2, 3, 1]]);
+    /// ```
+    pub fn longest_paths<T, F>(&self, source: usize, weight: F) -> Result<DagPaths<T>, Vec<Vec<usize>>>
+        where T: Copy + Default + PartialOrd + Add<Output = T>, F: FnMut(usize, usize) -> T
+    {
+        self.dag_paths_internal(source, weight, |new, cur| new > cur)
+    }
+}
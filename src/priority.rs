@@ -0,0 +1,223 @@
+use std::cmp::Ordering;
+
+use super::IndexGraph;
+
+/// A binary min-heap of vertex indices ordered by a user-supplied comparator
+///
+/// Vertices that compare equal are ordered by their index, so that the order
+/// in which vertices are popped never depends on the order they were pushed.
+struct Heap<F> {
+    data: Vec<usize>,
+    compare: F,
+}
+
+impl<F> Heap<F>
+    where F: FnMut(&usize, &usize) -> Ordering
+{
+    fn new(compare: F) -> Self {
+        Heap { data: Vec::new(), compare }
+    }
+
+    fn less(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.data[a], self.data[b]);
+        (self.compare)(&a, &b).then(a.cmp(&b)) == Ordering::Less
+    }
+
+    fn push(&mut self, idx: usize) {
+        self.data.push(idx);
+
+        // sift up
+        let mut pos = self.data.len() - 1;
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if !self.less(pos, parent) {
+                break
+            }
+
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        if self.data.is_empty() {
+            return None
+        }
+
+        let top = self.data.swap_remove(0);
+
+        // sift down
+        let mut pos = 0;
+        loop {
+            let left = 2 * pos + 1;
+            if left >= self.data.len() {
+                break
+            }
+
+            let right = left + 1;
+            let child = if right < self.data.len() && self.less(right, left) {
+                right
+            } else {
+                left
+            };
+
+            if !self.less(child, pos) {
+                break
+            }
+
+            self.data.swap(pos, child);
+            pos = child;
+        }
+
+        Some(top)
+    }
+}
+
+impl IndexGraph {
+    /// Perform topological sort on the graph, choosing ready vertices with a
+    /// comparator
+    ///
+    /// If the graph contains no cycles, finds the topological ordering of this
+    /// graph using Kahn's algorithm and returns it as `Some(sorted)`. Instead
+    /// of a queue, a priority queue ordered by `compare` is used, so that of
+    /// all vertices that are ready, the smallest according to `compare` is
+    /// always emitted first. Vertices that compare equal are emitted in index
+    /// order.
+    ///
+    /// This method runs in `O((V + E) log V)` time.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// This example schedules tasks with their estimated runtime, always
+    /// starting the longest task that is ready first.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let runtime = [1, 5, 3, 2];
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![3],
+    ///     vec![3],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let sorted = g.toposort_by(|&a, &b| runtime[b].cmp(&runtime[a]));
+    /// assert_eq!(sorted, Some(vec![1, 2, 0, 3]));
+    /// ```
+    pub fn toposort_by<F>(mut self, compare: F) -> Option<Vec<usize>>
+        where F: FnMut(&usize, &usize) -> Ordering
+    {
+        let mut heap = Heap::new(compare);
+        let mut sorted = Vec::with_capacity(self.vertices.len());
+
+        // Kahn's algorithm for toposort, using a min-heap instead of a queue
+
+        // enqueue vertices with in-degree zero
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            if vertex.in_degree == 0 {
+                heap.push(idx);
+            }
+        }
+
+        // add smallest vertex from heap to sorted list
+        // decrement in-degree of neighboring edges
+        // add to heap if in-degree zero
+        while let Some(idx) = heap.pop() {
+            sorted.push(idx);
+
+            for edge_idx in 0..self.vertices[idx].out_edges.len() {
+                let next_idx = self.vertices[idx].out_edges[edge_idx];
+
+                self.vertices[next_idx].in_degree -= 1;
+                if self.vertices[next_idx].in_degree == 0 {
+                    heap.push(next_idx);
+                }
+            }
+        }
+
+        // if every vertex appears in sorted list, sort is successful
+        if sorted.len() == self.vertices.len() {
+            Some(sorted)
+        } else {
+            None
+        }
+    }
+
+    /// Perform topological sort on the graph, choosing ready vertices with a
+    /// key extraction function
+    ///
+    /// Of all vertices that are ready, the one with the smallest key is always
+    /// emitted first. Vertices with equal keys are emitted in index order.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// This example schedules tasks with their estimated runtime, always
+    /// starting the longest task that is ready first.
+    ///
+    /// ```rust
+    /// use std::cmp::Reverse;
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let runtime = [1, 5, 3, 2];
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![3],
+    ///     vec![3],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let sorted = g.toposort_by_key(|&idx| Reverse(runtime[idx]));
+    /// assert_eq!(sorted, Some(vec![1, 2, 0, 3]));
+    /// ```
+    pub fn toposort_by_key<K, F>(self, mut f: F) -> Option<Vec<usize>>
+        where F: FnMut(&usize) -> K, K: Ord
+    {
+        self.toposort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Perform lexicographically smallest topological sort on the graph
+    ///
+    /// If the graph contains no cycles, finds the topological ordering of this
+    /// graph using Kahn's algorithm and returns it as `Some(sorted)`. Instead
+    /// of a queue, a priority queue is used, so that the smallest index of all
+    /// vertices that are ready is always emitted first.
+    ///
+    /// The resulting ordering is the lexicographically smallest topological
+    /// ordering of the graph, and does not depend on the order in which edges
+    /// were added. This method runs in `O((V + E) log V)` time.
+    ///
+    /// If the graph contains cycles, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// This example creates an `IndexGraph` of the example graph from the
+    /// Wikipedia page for
+    /// [Topological sorting](https://en.wikipedia.org/wiki/Topological_sorting)
+    /// and performs a lexicographically smallest topological sort.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![3],
+    ///     vec![3, 4],
+    ///     vec![4, 7],
+    ///     vec![5, 6, 7],
+    ///     vec![6],
+    ///     vec![],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// assert_eq!(g.clone().toposort(), Some(vec![0, 1, 2, 3, 4, 5, 7, 6]));
+    /// assert_eq!(g.toposort_lexical(), Some(vec![0, 1, 2, 3, 4, 5, 6, 7]));
+    /// ```
+    pub fn toposort_lexical(self) -> Option<Vec<usize>> {
+        self.toposort_by(|_, _| Ordering::Equal)
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::collections::VecDeque as Queue;
+
+use super::IndexGraph;
+
+/// The direction in which edges are followed from a set of vertices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Follow edges backwards, towards the vertices that have edges to the
+    /// starting vertices
+    Incoming,
+    /// Follow edges forwards, towards the vertices that the starting vertices
+    /// have edges to
+    Outgoing,
+}
+
+impl IndexGraph {
+    /// Perform topological sort on the part of the graph reachable from a set
+    /// of targets
+    ///
+    /// Collects the given targets and every vertex reachable from them by
+    /// following edges in the given direction, and finds the topological
+    /// ordering of the subgraph of these vertices using Kahn's algorithm.
+    /// Vertices that are not reachable are never visited, so this method runs
+    /// in time proportional to the size of the subgraph.
+    ///
+    /// If edges go from a dependency to the targets that use it, as in the
+    /// example of `IndexGraph::from_graph()`, `Direction::Incoming` sorts the
+    /// targets and everything they transitively depend on, and
+    /// `Direction::Outgoing` sorts the targets and everything that
+    /// transitively depends on them.
+    ///
+    /// If the subgraph contains cycles, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// This example creates an `IndexGraph` of the example graph from the
+    /// Wikipedia page for
+    /// [Topological sorting](https://en.wikipedia.org/wiki/Topological_sorting)
+    /// and sorts the parts of it that are connected to vertex 3.
+    ///
+    /// ```rust
+    /// use toposort_scc::{Direction, IndexGraph};
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![3],
+    ///     vec![3, 4],
+    ///     vec![4, 7],
+    ///     vec![5, 6, 7],
+    ///     vec![6],
+    ///     vec![],
+    ///     vec![],
+    ///     vec![]
+    /// ]);
+    ///
+    /// assert_eq!(g.toposort_from(&[3], Direction::Incoming), Some(vec![0, 1, 3]));
+    /// assert_eq!(g.toposort_from(&[3], Direction::Outgoing), Some(vec![3, 5, 6, 7]));
+    /// assert_eq!(g.toposort_from(&[4, 7], Direction::Incoming), Some(vec![0, 1, 2, 3, 4, 7]));
+    /// ```
+    pub fn toposort_from(&self, targets: &[usize], direction: Direction) -> Option<Vec<usize>> {
+        // in-degree within the subgraph of every collected vertex
+        let mut in_degree = HashMap::new();
+        let mut stack = Vec::new();
+
+        for &idx in targets {
+            if in_degree.insert(idx, 0).is_none() {
+                stack.push(idx);
+            }
+        }
+
+        // collect reachable vertices by depth-first search
+        while let Some(idx) = stack.pop() {
+            let edges = match direction {
+                Direction::Incoming => &self.vertices[idx].in_edges,
+                Direction::Outgoing => &self.vertices[idx].out_edges,
+            };
+
+            for &next_idx in edges {
+                if in_degree.insert(next_idx, 0).is_none() {
+                    stack.push(next_idx);
+                }
+            }
+        }
+
+        let mut collected: Vec<_> = in_degree.keys().copied().collect();
+        collected.sort_unstable();
+
+        for &idx in &collected {
+            for next_idx in &self.vertices[idx].out_edges {
+                if let Some(degree) = in_degree.get_mut(next_idx) {
+                    *degree += 1;
+                }
+            }
+        }
+
+        // Kahn's algorithm for toposort, restricted to collected vertices
+        let mut queue: Queue<_> = collected.iter()
+            .copied()
+            .filter(|idx| in_degree[idx] == 0)
+            .collect();
+        let mut sorted = Vec::with_capacity(collected.len());
+
+        while let Some(idx) = queue.pop_front() {
+            sorted.push(idx);
+
+            for next_idx in &self.vertices[idx].out_edges {
+                if let Some(degree) = in_degree.get_mut(next_idx) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        queue.push_back(*next_idx);
+                    }
+                }
+            }
+        }
+
+        // if every collected vertex appears in sorted list, sort is successful
+        if sorted.len() == collected.len() {
+            Some(sorted)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashSet;
+
+use super::bitset::BitSet;
+use super::IndexGraph;
+use super::SccPartition;
+
+/// The transitive reduction of an `IndexGraph`
+///
+/// Returned by `IndexGraph::transitive_reduction()`.
+#[derive(Debug, Clone)]
+pub struct TransitiveReduction {
+    /// A graph with the same vertices and the same reachability as the
+    /// original graph, containing only the edges that were kept
+    pub graph: IndexGraph,
+    /// Edges `(from, to)` of the original graph that were removed
+    ///
+    /// Parallel edges are listed once for every removed occurrence.
+    pub redundant: Vec<(usize, usize)>,
+}
+
+impl IndexGraph {
+    /// Internal method that selects edges that keep a strongly connected
+    /// component strongly connected
+    ///
+    /// Starts with the edges of a forward and a backward breadth-first search
+    /// tree from the first vertex of the component, and then drops every edge
+    /// that is not needed to keep the component strongly connected.
+    ///
+    /// `local` must map every vertex of the component to its index in the
+    /// component.
+    fn strongly_connected_edges_internal(&self, partition: &SccPartition, id: usize, local: &[usize]) -> Vec<(usize, usize)> {
+        let component = partition.component(id);
+        let in_component = |idx: usize| partition.component_of(idx) == id;
+
+        // edges of forward and backward search trees from the first vertex
+        let mut edges = Vec::new();
+        for forward in [true, false] {
+            let mut visited = vec![false; component.len()];
+            let mut queue = vec![component[0]];
+            visited[0] = true;
+
+            let mut pos = 0;
+            while pos < queue.len() {
+                let idx = queue[pos];
+                pos += 1;
+
+                let next = if forward {
+                    &self.vertices[idx].out_edges
+                } else {
+                    &self.vertices[idx].in_edges
+                };
+
+                for &next_idx in next {
+                    if in_component(next_idx) && !visited[local[next_idx]] {
+                        visited[local[next_idx]] = true;
+                        queue.push(next_idx);
+                        edges.push(if forward { (idx, next_idx) } else { (next_idx, idx) });
+                    }
+                }
+            }
+        }
+
+        edges.sort_unstable();
+        edges.dedup();
+
+        // drop edges while the component stays strongly connected, which is
+        // the case as long as the target of the dropped edge can still be
+        // reached from its source
+        let mut adjacency = vec![Vec::new(); component.len()];
+        for (edge_idx, &(from, _)) in edges.iter().enumerate() {
+            adjacency[local[from]].push(edge_idx);
+        }
+
+        let mut kept = vec![true; edges.len()];
+        let mut visited = vec![usize::MAX; component.len()];
+        let mut stack = Vec::new();
+
+        for (edge_idx, &(from, to)) in edges.iter().enumerate() {
+            kept[edge_idx] = false;
+            visited[local[from]] = edge_idx;
+            stack.clear();
+            stack.push(local[from]);
+
+            let mut found = false;
+            while let Some(idx) = stack.pop() {
+                for &next_edge_idx in &adjacency[idx] {
+                    let next_idx = local[edges[next_edge_idx].1];
+                    if kept[next_edge_idx] && visited[next_idx] != edge_idx {
+                        visited[next_idx] = edge_idx;
+                        stack.push(next_idx);
+                    }
+                }
+
+                if visited[local[to]] == edge_idx {
+                    found = true;
+                    break
+                }
+            }
+
+            kept[edge_idx] = !found;
+        }
+
+        edges.iter()
+            .zip(kept)
+            .filter(|&(_, kept)| kept)
+            .map(|(&edge, _)| edge)
+            .collect()
+    }
+
+    /// Remove every edge that is implied by other paths
+    ///
+    /// Returns a graph with the same vertices and the same reachability as
+    /// this graph, together with the list of removed edges.
+    ///
+    /// If the graph contains no cycles, the result is the unique transitive
+    /// reduction of the graph, and every removed edge `(a, b)` is implied by
+    /// another path from `a` to `b`.
+    ///
+    /// If the graph contains cycles, the result is a minimal equivalent graph:
+    /// between strongly connected components, only the edges of the transitive
+    /// reduction of the condensation are kept, and within every strongly
+    /// connected component, only enough edges are kept so that it stays
+    /// strongly connected and no further edge can be dropped. Since finding a
+    /// minimum equivalent graph is NP-hard, there may be equivalent graphs
+    /// with fewer edges.
+    ///
+    /// This method uses `O(C^2)` additional space, where `C` is the number of
+    /// strongly connected components. Within a strongly connected component
+    /// of `V'` vertices, up to `2 * V'` candidate edges are each checked with a
+    /// depth-first search, which takes `O(V'^2)` time in the worst case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1, 2, 3],
+    ///     vec![3],
+    ///     vec![3],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let reduction = g.transitive_reduction();
+    /// assert_eq!(reduction.redundant, vec![(0, 3)]);
+    ///
+    /// let mut g2 = g.clone();
+    /// g2.add_edge(3, 0); // cycle [0, 1, 2, 3]
+    ///
+    /// let reduction = g2.transitive_reduction();
+    /// assert_eq!(reduction.redundant, vec![(0, 3)]);
+    /// assert_eq!(reduction.graph.scc_partition().len(), 1);
+    /// ```
+    pub fn transitive_reduction(&self) -> TransitiveReduction {
+        let condensation = self.condensation();
+        let partition = condensation.partition();
+        let components = condensation.graph();
+        let len = condensation.len();
+
+        // transitive reduction of the condensation, components are numbered
+        // in topological order, so children are visited in topological order
+        // if sorted by id, and an edge is only needed if its target is not
+        // reachable through an earlier child
+        let reach = condensation.reach_internal();
+        let mut needed_between = HashSet::new();
+
+        for id in 0..len {
+            let mut children = components[id].out_edges.clone();
+            children.sort_unstable();
+
+            let mut id_reach = BitSet::new(len);
+            for next_id in children {
+                if !id_reach.contains(next_id) {
+                    needed_between.insert((id, next_id));
+                    id_reach.insert(next_id);
+                    id_reach.union_with(&reach[next_id]);
+                }
+            }
+        }
+
+        // position of every vertex in its component
+        let mut local = vec![0; self.vertices.len()];
+        for component in partition.components() {
+            for (local_idx, &idx) in component.iter().enumerate() {
+                local[idx] = local_idx;
+            }
+        }
+
+        // edges needed within components
+        let mut needed_within = HashSet::new();
+        for id in 0..len {
+            let component = partition.component(id);
+
+            if component.len() > 1 {
+                let edges = self.strongly_connected_edges_internal(partition, id, &local);
+                needed_within.extend(edges);
+            } else if partition.is_cyclic(id) {
+                needed_within.insert((component[0], component[0]));
+            }
+        }
+
+        // keep the first original edge for every needed edge
+        let mut graph = IndexGraph::with_vertices(self.vertices.len());
+        let mut redundant = Vec::new();
+
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            for &next_idx in &vertex.out_edges {
+                let id = partition.component_of(idx);
+                let next_id = partition.component_of(next_idx);
+
+                let kept = if id == next_id {
+                    needed_within.remove(&(idx, next_idx))
+                } else {
+                    needed_between.remove(&(id, next_id))
+                };
+
+                if kept {
+                    graph.add_edge(idx, next_idx);
+                } else {
+                    redundant.push((idx, next_idx));
+                }
+            }
+        }
+
+        TransitiveReduction { graph, redundant }
+    }
+}
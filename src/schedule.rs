@@ -0,0 +1,117 @@
+use std::ops::{Add, Sub};
+
+use super::IndexGraph;
+
+/// A schedule of the vertices of an `IndexGraph` with durations
+///
+/// Returned by `IndexGraph::critical_path()`. All times are measured from the
+/// start of the earliest vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriticalPath<T> {
+    /// The earliest time every vertex can start
+    pub earliest_start: Vec<T>,
+    /// The latest time every vertex can start without delaying the end of the
+    /// schedule
+    pub latest_start: Vec<T>,
+    /// The difference between the latest and earliest start of every vertex
+    pub slack: Vec<T>,
+    /// A longest path through the graph, whose vertices all have no slack
+    pub path: Vec<usize>,
+    /// The total duration of the schedule, which is the length of `path`
+    pub length: T,
+}
+
+impl IndexGraph {
+    /// Find the critical path of the graph with per-vertex durations
+    ///
+    /// Every vertex `v` takes `durations[v]` to complete, and can only start
+    /// once all vertices with edges to it have completed. Computes the
+    /// earliest and latest start time and the slack of every vertex in a
+    /// forward and a backward pass over the topological ordering of the graph,
+    /// and returns them together with a critical path, a chain of vertices
+    /// that determines the total duration.
+    ///
+    /// `T::default()` is used as the zero duration.
+    ///
+    /// If the graph contains cycles, finds the strongly connected components
+    /// of this graph using Kosaraju's algorithm and returns them as
+    /// `Err(cycles)`.
+    ///
+    /// # Example
+    ///
+    /// This example creates a graph of build targets with their build times.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![2],
+    ///     vec![2, 3],
+    ///     vec![4],
+    ///     vec![4],
+    ///     vec![]
+    /// ]);
+    ///
+    /// let schedule = g.critical_path(&[3, 1, 2, 1, 1]).unwrap();
+    ///
+    /// assert_eq!(schedule.path, vec![0, 2, 4]);
+    /// assert_eq!(schedule.length, 6);
+    /// assert_eq!(schedule.earliest_start, vec![0, 0, 3, 1, 5]);
+    /// assert_eq!(schedule.latest_start, vec![0, 2, 3, 4, 5]);
+    /// assert_eq!(schedule.slack, vec![0, 2, 0, 3, 0]);
+    /// ```
+    pub fn critical_path<T>(&self, durations: &[T]) -> Result<CriticalPath<T>, Vec<Vec<usize>>>
+        where T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>
+    {
+        let sorted = self.clone().toposort_or_scc()?;
+        let zero = T::default();
+        let len = self.vertices.len();
+
+        let max = |a: T, b: T| if b > a { b } else { a };
+        let min = |a: T, b: T| if b < a { b } else { a };
+
+        // forward pass, earliest start is the latest finish of any predecessor
+        let mut earliest_start = vec![zero; len];
+        for &idx in &sorted {
+            earliest_start[idx] = self.vertices[idx].in_edges.iter()
+                .map(|&prev_idx| earliest_start[prev_idx] + durations[prev_idx])
+                .fold(zero, max);
+        }
+
+        let length = (0..len)
+            .map(|idx| earliest_start[idx] + durations[idx])
+            .fold(zero, max);
+
+        // backward pass, latest finish is the earliest latest start of any
+        // successor
+        let mut latest_start = vec![zero; len];
+        for &idx in sorted.iter().rev() {
+            let latest_finish = self.vertices[idx].out_edges.iter()
+                .map(|&next_idx| latest_start[next_idx])
+                .fold(length, min);
+
+            latest_start[idx] = latest_finish - durations[idx];
+        }
+
+        let slack = (0..len)
+            .map(|idx| latest_start[idx] - earliest_start[idx])
+            .collect();
+
+        // walk back from the vertex that finishes last along predecessors that
+        // finish exactly when their successor starts
+        let mut path = Vec::new();
+        let mut cur_idx = (0..len).find(|&idx| earliest_start[idx] + durations[idx] == length);
+
+        while let Some(idx) = cur_idx {
+            path.push(idx);
+
+            cur_idx = self.vertices[idx].in_edges.iter()
+                .copied()
+                .find(|&prev_idx| earliest_start[prev_idx] + durations[prev_idx] == earliest_start[idx]);
+        }
+
+        path.reverse();
+
+        Ok(CriticalPath { earliest_start, latest_start, slack, path, length })
+    }
+}
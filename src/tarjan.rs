@@ -0,0 +1,137 @@
+use super::IndexGraph;
+
+impl IndexGraph {
+    /// Find strongly connected components in a single pass
+    ///
+    /// Finds all strongly connected components of this graph using Tarjan's
+    /// algorithm in `O(V + E)` time and `O(V)` additional space. Unlike
+    /// `.scc()` and `.scc_partition()`, this method only follows outgoing edges
+    /// and does not need a second depth-first search in opposite edge
+    /// direction.
+    ///
+    /// Every vertex is assigned to a component, including vertices that are
+    /// not part of any cycle. Components are returned in reverse topological
+    /// order of the condensation of the graph, so every edge between two
+    /// different components goes from a component that appears later in the
+    /// list to one that appears earlier.
+    ///
+    /// # Example
+    ///
+    /// This example creates an `IndexGraph` of the example graph from the
+    /// Wikipedia page for
+    /// [Strongly connected components](https://en.wikipedia.org/wiki/Strongly_connected_component),
+    /// finds the strongly connected components and compares them to the
+    /// components found by Kosaraju's algorithm.
+    ///
+    /// ```rust
+    /// use toposort_scc::IndexGraph;
+    ///
+    /// let g = IndexGraph::from_adjacency_list(&vec![
+    ///     vec![1],
+    ///     vec![2, 4, 5],
+    ///     vec![3, 6],
+    ///     vec![2, 7],
+    ///     vec![0, 5],
+    ///     vec![6],
+    ///     vec![5],
+    ///     vec![3, 6]
+    /// ]);
+    ///
+    /// let tarjan = g.scc_tarjan();
+    /// assert_eq!(tarjan, vec![vec![5, 6], vec![7, 3, 2], vec![4, 1, 0]]);
+    ///
+    /// let mut kosaraju = g.scc_partition().into_components();
+    /// kosaraju.reverse();
+    ///
+    /// let normalize = |mut components: Vec<Vec<usize>>| {
+    ///     components.iter_mut().for_each(|c| c.sort());
+    ///     components.sort();
+    ///     components
+    /// };
+    /// assert_eq!(normalize(tarjan), normalize(kosaraju));
+    ///
+    /// // self-loops, isolated vertices, a cycle not reachable from vertex 0,
+    /// // and parallel edges
+    /// let graphs = vec![
+    ///     vec![vec![0], vec![1, 2], vec![], vec![3, 3, 0]],
+    ///     vec![vec![], vec![], vec![]],
+    ///     vec![vec![1], vec![], vec![3], vec![4], vec![2, 1]],
+    ///     vec![vec![1, 1], vec![0, 0, 2], vec![2, 2], vec![]],
+    /// ];
+    ///
+    /// for adjacency_list in &graphs {
+    ///     let g = IndexGraph::from_adjacency_list(adjacency_list);
+    ///     let tarjan = g.scc_tarjan();
+    ///     let kosaraju = g.scc_partition().into_components();
+    ///     assert_eq!(normalize(tarjan), normalize(kosaraju));
+    /// }
+    /// ```
+    pub fn scc_tarjan(&self) -> Vec<Vec<usize>> {
+        let len = self.vertices.len();
+
+        // Tarjan's algorithm for strongly connected components
+
+        let mut index = vec![usize::MAX; len];
+        let mut lowlink = vec![0; len];
+        let mut on_stack = vec![false; len];
+        let mut next_index = 0;
+
+        let mut stack = Vec::new();
+        let mut dfs_stack = Vec::new();
+        let mut components = Vec::new();
+
+        for root_idx in 0..len {
+            if index[root_idx] != usize::MAX {
+                continue
+            }
+
+            index[root_idx] = next_index;
+            lowlink[root_idx] = next_index;
+            next_index += 1;
+            on_stack[root_idx] = true;
+            stack.push(root_idx);
+            dfs_stack.push((root_idx, 0));
+
+            while let Some((idx, edge_idx)) = dfs_stack.pop() {
+                if edge_idx < self.vertices[idx].out_edges.len() {
+                    dfs_stack.push((idx, edge_idx + 1));
+
+                    let next_idx = self.vertices[idx].out_edges[edge_idx];
+                    if index[next_idx] == usize::MAX {
+                        index[next_idx] = next_index;
+                        lowlink[next_idx] = next_index;
+                        next_index += 1;
+                        on_stack[next_idx] = true;
+                        stack.push(next_idx);
+                        dfs_stack.push((next_idx, 0));
+                    } else if on_stack[next_idx] {
+                        lowlink[idx] = lowlink[idx].min(index[next_idx]);
+                    }
+                } else {
+                    // propagate lowlink to the parent in the search tree
+                    if let Some(&(parent_idx, _)) = dfs_stack.last() {
+                        lowlink[parent_idx] = lowlink[parent_idx].min(lowlink[idx]);
+                    }
+
+                    // vertex is the root of a component, pop it off the stack
+                    if lowlink[idx] == index[idx] {
+                        let mut component = Vec::new();
+
+                        while let Some(member_idx) = stack.pop() {
+                            on_stack[member_idx] = false;
+                            component.push(member_idx);
+
+                            if member_idx == idx {
+                                break
+                            }
+                        }
+
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        components
+    }
+}